@@ -0,0 +1,537 @@
+use crate::space::ColorSpace;
+use crate::xyz::Matrix;
+use crate::{LinearSrgb, Srgb, XyzD65};
+use std::f64::consts::PI;
+
+/// A color in the CAM16 color appearance model.
+///
+/// Colors are viewed in the default conditions of Material Color Utilities: a D65 white point,
+/// an adapting luminance of about 11.7 cd/m², a background of L* 50 and an average surround.
+///
+/// <https://github.com/material-foundation/material-color-utilities>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cam16 {
+    /// The lightness component J, from 0 to 100.
+    pub lightness: f32,
+    /// The chroma component C.
+    pub chroma: f32,
+    /// The hue component in degrees.
+    pub hue: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+/// A color in the HCT color space of Material Design: CAM16 hue and chroma with CIE L* tone.
+///
+/// Converting to another color space finds the sRGB color with the given hue and tone whose
+/// chroma is closest to the given chroma, so chromas outside the sRGB gamut are reduced.
+///
+/// <https://material.io/blog/science-of-color-design>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hct {
+    /// The CAM16 hue in degrees.
+    pub hue: f32,
+    /// The CAM16 chroma.
+    pub chroma: f32,
+    /// The CIE L* tone, from 0 for black to 100 for white.
+    pub tone: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+impl Cam16 {
+    pub fn new(lightness: f32, chroma: f32, hue: f32, alpha: f32) -> Cam16 {
+        Cam16 {
+            lightness,
+            chroma,
+            hue,
+            alpha,
+        }
+    }
+
+    /// Returns the coordinates J*, a* and b* in the CAM16-UCS uniform color space.
+    pub fn ucs(&self) -> [f32; 3] {
+        let vc = &DEFAULT_VIEWING_CONDITIONS;
+        let j = f64::from(self.lightness);
+        let m = f64::from(self.chroma) * vc.fl_root;
+        let h = f64::from(self.hue).to_radians();
+        let jstar = (1. + 100. * 0.007) * j / (1. + 0.007 * j);
+        let mstar = (1. + 0.0228 * m).ln() / 0.0228;
+        [
+            jstar as f32,
+            (mstar * h.cos()) as f32,
+            (mstar * h.sin()) as f32,
+        ]
+    }
+
+    /// Returns the color difference in CAM16-UCS.
+    pub fn distance(&self, other: &Cam16) -> f32 {
+        let [j1, a1, b1] = self.ucs().map(f64::from);
+        let [j2, a2, b2] = other.ucs().map(f64::from);
+        let de = ((j1 - j2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt();
+        (1.41 * de.powf(0.63)) as f32
+    }
+}
+
+impl Hct {
+    pub fn new(hue: f32, chroma: f32, tone: f32, alpha: f32) -> Hct {
+        Hct {
+            hue,
+            chroma,
+            tone,
+            alpha,
+        }
+    }
+}
+
+// Material Color Utilities uses its own sRGB matrix, which is kept for identical results.
+const SRGB_TO_XYZ: Matrix = [
+    [0.41233895, 0.35762064, 0.18051042],
+    [0.2126, 0.7152, 0.0722],
+    [0.01932141, 0.11916382, 0.95034478],
+];
+
+const WHITE_POINT_D65: [f64; 3] = [95.047, 100.0, 108.883];
+
+fn multiply(m: &Matrix, v: [f64; 3]) -> [f64; 3] {
+    let row = |r: &[f64; 3]| r[0] * v[0] + r[1] * v[1] + r[2] * v[2];
+    [row(&m[0]), row(&m[1]), row(&m[2])]
+}
+
+/// Converts a gamma-encoded sRGB component from 0 to 1 to linear light from 0 to 100.
+fn linearized(component: f64) -> f64 {
+    let linear = if component <= 0.040449936 {
+        component / 12.92
+    } else {
+        ((component + 0.055) / 1.055).powf(2.4)
+    };
+    linear * 100.
+}
+
+/// Converts a linear-light sRGB component from 0 to 100 to gamma-encoded sRGB from 0 to 255.
+fn delinearized(component: f64) -> f64 {
+    let normalized = component / 100.;
+    let delinearized = if normalized <= 0.0031308 {
+        normalized * 12.92
+    } else {
+        1.055 * normalized.powf(1. / 2.4) - 0.055
+    };
+    delinearized * 255.
+}
+
+/// Returns the relative luminance from 0 to 100 of a CIE L* tone.
+pub(crate) fn y_from_lstar(lstar: f64) -> f64 {
+    const KAPPA: f64 = 24389. / 27.;
+    const EPSILON: f64 = 216. / 24389.;
+    let ft = (lstar + 16.) / 116.;
+    let ft3 = ft * ft * ft;
+    100. * if ft3 > EPSILON {
+        ft3
+    } else {
+        (116. * ft - 16.) / KAPPA
+    }
+}
+
+/// Returns the CIE L* tone of a relative luminance from 0 to 100.
+pub(crate) fn lstar_from_y(y: f64) -> f64 {
+    const KAPPA: f64 = 24389. / 27.;
+    const EPSILON: f64 = 216. / 24389.;
+    let t = y / 100.;
+    let f = if t > EPSILON {
+        t.cbrt()
+    } else {
+        (KAPPA * t + 16.) / 116.
+    };
+    116. * f - 16.
+}
+
+/// The CAM16 viewing conditions.
+struct ViewingConditions {
+    n: f64,
+    aw: f64,
+    nbb: f64,
+    ncb: f64,
+    c: f64,
+    nc: f64,
+    fl_root: f64,
+    z: f64,
+    /// The matrix from linear sRGB, from 0 to 100, to discounted and scaled cone responses.
+    scaled_discount_from_linrgb: Matrix,
+    /// The inverse of `scaled_discount_from_linrgb`.
+    linrgb_from_scaled_discount: Matrix,
+}
+
+// The default viewing conditions of Material Color Utilities: a D65 white point, an adapting
+// luminance of 200 / π times the luminance of L* 50, a background of L* 50 and an average
+// surround. They are precomputed, as in Material Color Utilities.
+#[rustfmt::skip]
+const DEFAULT_VIEWING_CONDITIONS: ViewingConditions = ViewingConditions {
+    n: 0.18418651851244416,
+    aw: 29.980997194447333,
+    nbb: 1.0169191804458755,
+    ncb: 1.0169191804458755,
+    c: 0.69,
+    nc: 1.0,
+    fl_root: 0.7894826179304937,
+    z: 1.909169568483652,
+    scaled_discount_from_linrgb: [
+        [0.001200833568784504, 0.0023896944921708895, 0.00027957428858611246],
+        [0.0005891086651375999, 0.0029785502573438753, 0.00032706661040083983],
+        [0.0001014669249164057, 0.0005364214359186694, 0.0032979401770712076],
+    ],
+    linrgb_from_scaled_discount: [
+        [1373.2198709594238, -1100.4251190754833, -7.278681089101196],
+        [-271.8159690779032, 559.6580465940737, -32.46047482791196],
+        [1.962289959966585, -57.173814538844034, 308.72331978123856],
+    ],
+};
+
+fn chromatic_adaptation(component: f64) -> f64 {
+    let af = component.abs().powf(0.42);
+    component.signum() * 400. * af / (af + 27.13)
+}
+
+fn inverse_chromatic_adaptation(adapted: f64) -> f64 {
+    let abs = adapted.abs();
+    let base = (27.13 * abs / (400. - abs)).max(0.);
+    adapted.signum() * base.powf(1. / 0.42)
+}
+
+/// Returns the CAM16 lightness, chroma and hue of a color in linear sRGB from 0 to 100.
+fn cam16_from_linrgb(linrgb: [f64; 3]) -> [f64; 3] {
+    let vc = &DEFAULT_VIEWING_CONDITIONS;
+    let [r, g, b] = multiply(&vc.scaled_discount_from_linrgb, linrgb).map(chromatic_adaptation);
+
+    let a = (11. * r + -12. * g + b) / 11.;
+    let bb = (r + g - 2. * b) / 9.;
+    let u = (20. * r + 20. * g + 21. * b) / 20.;
+    let p2 = (40. * r + 20. * g + b) / 20.;
+    let hue = bb.atan2(a).to_degrees().rem_euclid(360.);
+
+    let ac = p2 * vc.nbb;
+    let j = 100. * (ac / vc.aw).powf(vc.c * vc.z);
+    let hue_prime = if hue < 20.14 { hue + 360. } else { hue };
+    let e_hue = 0.25 * ((hue_prime.to_radians() + 2.).cos() + 3.8);
+    let p1 = 50000. / 13. * e_hue * vc.nc * vc.ncb;
+    let t = p1 * a.hypot(bb) / (u + 0.305);
+    let alpha = t.powf(0.9) * (1.64 - 0.29_f64.powf(vc.n)).powf(0.73);
+    let chroma = alpha * (j / 100.).sqrt();
+    if j.is_nan() || chroma.is_nan() {
+        return [0., 0., 0.];
+    }
+    [j, chroma, hue]
+}
+
+/// Returns the linear sRGB color, from 0 to 100, of a CAM16 lightness, chroma and hue.
+fn linrgb_from_cam16([j, chroma, hue]: [f64; 3]) -> [f64; 3] {
+    let vc = &DEFAULT_VIEWING_CONDITIONS;
+    let alpha = if chroma == 0. || j == 0. {
+        0.
+    } else {
+        chroma / (j / 100.).sqrt()
+    };
+    let t = (alpha / (1.64 - 0.29_f64.powf(vc.n)).powf(0.73)).powf(1. / 0.9);
+    let h = hue.to_radians();
+    let e_hue = 0.25 * ((h + 2.).cos() + 3.8);
+    let ac = vc.aw * (j / 100.).powf(1. / vc.c / vc.z);
+    let p1 = e_hue * (50000. / 13.) * vc.nc * vc.ncb;
+    let p2 = ac / vc.nbb;
+    let (h_sin, h_cos) = h.sin_cos();
+    let gamma = 23. * (p2 + 0.305) * t / (23. * p1 + 11. * t * h_cos + 108. * t * h_sin);
+    let a = gamma * h_cos;
+    let b = gamma * h_sin;
+    let adapted = [
+        (460. * p2 + 451. * a + 288. * b) / 1403.,
+        (460. * p2 - 891. * a - 261. * b) / 1403.,
+        (460. * p2 - 220. * a - 6300. * b) / 1403.,
+    ];
+    multiply(
+        &vc.linrgb_from_scaled_discount,
+        adapted.map(inverse_chromatic_adaptation),
+    )
+}
+
+fn linrgb_from_srgb(srgb: Srgb) -> [f64; 3] {
+    [srgb.red, srgb.green, srgb.blue].map(|c| linearized(f64::from(c)))
+}
+
+fn lstar_from_linrgb(linrgb: [f64; 3]) -> f64 {
+    lstar_from_y(multiply(&SRGB_TO_XYZ, linrgb)[1])
+}
+
+fn hct_from_linrgb(linrgb: [f64; 3]) -> [f64; 3] {
+    let [_, chroma, hue] = cam16_from_linrgb(linrgb);
+    [hue, chroma, lstar_from_linrgb(linrgb)]
+}
+
+/// Returns the HCT hue, chroma and tone of an 8-bit sRGB color.
+pub(crate) fn hct_from_rgb8(rgb: [u8; 3]) -> [f64; 3] {
+    hct_from_linrgb(rgb.map(|c| linearized(f64::from(c) / 255.)))
+}
+
+/// Returns the 8-bit sRGB color closest to an HCT hue, chroma and tone, as Material Color
+/// Utilities does.
+pub(crate) fn rgb8_from_hct(hue: f64, chroma: f64, tone: f64) -> [u8; 3] {
+    solve(hue, chroma, tone).map(|c| delinearized(c).round().clamp(0., 255.) as u8)
+}
+
+/// Returns the CIE Lab color of an 8-bit sRGB color, relative to D65 as in Material Color
+/// Utilities.
+pub(crate) fn lab_from_rgb8(rgb: [u8; 3]) -> [f64; 3] {
+    let xyz = multiply(&SRGB_TO_XYZ, rgb.map(|c| linearized(f64::from(c) / 255.)));
+    let [fx, fy, fz] = [0, 1, 2].map(|i| lab_f(xyz[i] / WHITE_POINT_D65[i]));
+    [116. * fy - 16., 500. * (fx - fy), 200. * (fy - fz)]
+}
+
+fn lab_f(t: f64) -> f64 {
+    if t > 216. / 24389. {
+        t.cbrt()
+    } else {
+        (24389. / 27. * t + 16.) / 116.
+    }
+}
+
+impl From<Srgb> for Cam16 {
+    fn from(srgb: Srgb) -> Self {
+        let [j, chroma, hue] = cam16_from_linrgb(linrgb_from_srgb(srgb));
+        Cam16::new(j as f32, chroma as f32, hue as f32, srgb.alpha)
+    }
+}
+
+impl From<Srgb> for Hct {
+    fn from(srgb: Srgb) -> Self {
+        let [hue, chroma, tone] = hct_from_linrgb(linrgb_from_srgb(srgb));
+        Hct::new(hue as f32, chroma as f32, tone as f32, srgb.alpha)
+    }
+}
+
+impl ColorSpace for Cam16 {
+    fn to_xyz(self) -> XyzD65 {
+        let color = [self.lightness, self.chroma, self.hue].map(f64::from);
+        let [r, g, b] = linrgb_from_cam16(color).map(|c| (c / 100.) as f32);
+        LinearSrgb::new(r, g, b, self.alpha).to_xyz()
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        let linear = LinearSrgb::from_xyz(xyz);
+        let linrgb = [linear.red, linear.green, linear.blue].map(|c| f64::from(c) * 100.);
+        let [j, chroma, hue] = cam16_from_linrgb(linrgb);
+        Cam16::new(j as f32, chroma as f32, hue as f32, xyz.alpha)
+    }
+}
+
+impl ColorSpace for Hct {
+    fn to_xyz(self) -> XyzD65 {
+        let color = [self.hue, self.chroma, self.tone].map(f64::from);
+        let [r, g, b] = solve(color[0], color[1], color[2]).map(|c| (c / 100.) as f32);
+        LinearSrgb::new(r, g, b, self.alpha).to_xyz()
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        let linear = LinearSrgb::from_xyz(xyz);
+        let linrgb = [linear.red, linear.green, linear.blue].map(|c| f64::from(c) * 100.);
+        let [hue, chroma, tone] = hct_from_linrgb(linrgb);
+        Hct::new(hue as f32, chroma as f32, tone as f32, xyz.alpha)
+    }
+}
+
+// The HCT solver of Material Color Utilities, which finds the linear sRGB color of an HCT color.
+//
+// https://github.com/material-foundation/material-color-utilities/blob/main/java/hct/HctSolver.java
+
+const Y_FROM_LINRGB: [f64; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Returns the linear sRGB color, from 0 to 100, with the given hue and tone and the chroma
+/// closest to the given chroma.
+fn solve(hue: f64, chroma: f64, lstar: f64) -> [f64; 3] {
+    if chroma < 0.0001 || !(0.0001..=99.9999).contains(&lstar) {
+        return [y_from_lstar(lstar); 3];
+    }
+    let hue = hue.rem_euclid(360.).to_radians();
+    let y = y_from_lstar(lstar);
+    let vc = &DEFAULT_VIEWING_CONDITIONS;
+    find_result_by_j(vc, hue, chroma, y).unwrap_or_else(|| bisect_to_limit(vc, y, hue))
+}
+
+fn find_result_by_j(vc: &ViewingConditions, hue: f64, chroma: f64, y: f64) -> Option<[f64; 3]> {
+    // Initial estimate of j.
+    let mut j = y.sqrt() * 11.;
+    let t_inner_coeff = 1. / (1.64 - 0.29_f64.powf(vc.n)).powf(0.73);
+    let e_hue = 0.25 * ((hue + 2.).cos() + 3.8);
+    let p1 = e_hue * (50000. / 13.) * vc.nc * vc.ncb;
+    let (h_sin, h_cos) = hue.sin_cos();
+    for iteration in 0..5 {
+        let j_normalized = j / 100.;
+        let alpha = if chroma == 0. || j == 0. {
+            0.
+        } else {
+            chroma / j_normalized.sqrt()
+        };
+        let t = (alpha * t_inner_coeff).powf(1. / 0.9);
+        let ac = vc.aw * j_normalized.powf(1. / vc.c / vc.z);
+        let p2 = ac / vc.nbb;
+        let gamma = 23. * (p2 + 0.305) * t / (23. * p1 + 11. * t * h_cos + 108. * t * h_sin);
+        let a = gamma * h_cos;
+        let b = gamma * h_sin;
+        let adapted = [
+            (460. * p2 + 451. * a + 288. * b) / 1403.,
+            (460. * p2 - 891. * a - 261. * b) / 1403.,
+            (460. * p2 - 220. * a - 6300. * b) / 1403.,
+        ];
+        let linrgb = multiply(
+            &vc.linrgb_from_scaled_discount,
+            adapted.map(inverse_chromatic_adaptation),
+        );
+        if linrgb.iter().any(|c| *c < 0.) {
+            return None;
+        }
+        let fnj = dot(Y_FROM_LINRGB, linrgb);
+        if fnj <= 0. {
+            return None;
+        }
+        if iteration == 4 || (fnj - y).abs() < 0.002 {
+            if linrgb.iter().any(|c| *c > 100.01) {
+                return None;
+            }
+            return Some(linrgb);
+        }
+        // Iterates with Newton's method, using 2 * fn(j) / j as the approximation of fn'(j).
+        j -= (fnj - y) * j / (2. * fnj);
+    }
+    None
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Returns the hue in radians of a linear sRGB color.
+fn hue_of(vc: &ViewingConditions, linrgb: [f64; 3]) -> f64 {
+    let [r, g, b] = multiply(&vc.scaled_discount_from_linrgb, linrgb).map(chromatic_adaptation);
+    let a = (11. * r + -12. * g + b) / 11.;
+    let b = (r + g - 2. * b) / 9.;
+    b.atan2(a)
+}
+
+fn sanitize_radians(angle: f64) -> f64 {
+    (angle + PI * 8.) % (PI * 2.)
+}
+
+fn are_in_cyclic_order(a: f64, b: f64, c: f64) -> bool {
+    sanitize_radians(b - a) < sanitize_radians(c - a)
+}
+
+/// Returns the nth vertex of the polygon where the plane of luminance `y` intersects the RGB
+/// cube, or `None` if the edge does not intersect it.
+fn nth_vertex(y: f64, n: usize) -> Option<[f64; 3]> {
+    let [kr, kg, kb] = Y_FROM_LINRGB;
+    let coord_a = if n % 4 <= 1 { 0. } else { 100. };
+    let coord_b = if n % 2 == 1 { 100. } else { 0. };
+    let vertex = if n < 4 {
+        let (g, b) = (coord_a, coord_b);
+        [(y - g * kg - b * kb) / kr, g, b]
+    } else if n < 8 {
+        let (b, r) = (coord_a, coord_b);
+        [r, (y - r * kr - b * kb) / kg, b]
+    } else {
+        let (r, g) = (coord_a, coord_b);
+        [r, g, (y - r * kr - g * kg) / kb]
+    };
+    let bounded = |x: f64| (0. ..=100.).contains(&x);
+    if vertex.iter().all(|c| bounded(*c)) {
+        Some(vertex)
+    } else {
+        None
+    }
+}
+
+/// Finds the segment of the luminance plane's polygon that contains the target hue.
+fn bisect_to_segment(vc: &ViewingConditions, y: f64, target_hue: f64) -> [[f64; 3]; 2] {
+    let mut left = [-1.; 3];
+    let mut left_hue = 0.;
+    let mut right = left;
+    let mut right_hue = 0.;
+    let mut initialized = false;
+    let mut uncut = true;
+    for n in 0..12 {
+        let mid = match nth_vertex(y, n) {
+            Some(mid) => mid,
+            None => continue,
+        };
+        let mid_hue = hue_of(vc, mid);
+        if !initialized {
+            left = mid;
+            right = mid;
+            left_hue = mid_hue;
+            right_hue = mid_hue;
+            initialized = true;
+            continue;
+        }
+        if uncut || are_in_cyclic_order(left_hue, mid_hue, right_hue) {
+            uncut = false;
+            if are_in_cyclic_order(left_hue, target_hue, mid_hue) {
+                right = mid;
+                right_hue = mid_hue;
+            } else {
+                left = mid;
+                left_hue = mid_hue;
+            }
+        }
+    }
+    [left, right]
+}
+
+fn critical_plane_below(x: f64) -> i32 {
+    (x - 0.5).floor() as i32
+}
+
+fn critical_plane_above(x: f64) -> i32 {
+    (x - 0.5).ceil() as i32
+}
+
+/// Returns the linear value, from 0 to 100, at which an 8-bit sRGB component changes from `i`
+/// to `i + 1`.
+fn critical_plane(i: i32) -> f64 {
+    linearized((f64::from(i) + 0.5) / 255.)
+}
+
+/// Finds the color on the edge of the sRGB gamut with the given luminance and hue.
+fn bisect_to_limit(vc: &ViewingConditions, y: f64, target_hue: f64) -> [f64; 3] {
+    let [mut left, mut right] = bisect_to_segment(vc, y, target_hue);
+    let mut left_hue = hue_of(vc, left);
+    for axis in 0..3 {
+        if left[axis] == right[axis] {
+            continue;
+        }
+        let (mut l_plane, mut r_plane) = if left[axis] < right[axis] {
+            (
+                critical_plane_below(delinearized(left[axis])),
+                critical_plane_above(delinearized(right[axis])),
+            )
+        } else {
+            (
+                critical_plane_above(delinearized(left[axis])),
+                critical_plane_below(delinearized(right[axis])),
+            )
+        };
+        for _ in 0..8 {
+            if (r_plane - l_plane).abs() <= 1 {
+                break;
+            }
+            let m_plane = (l_plane + r_plane).div_euclid(2);
+            let coordinate = critical_plane(m_plane);
+            let t = (coordinate - left[axis]) / (right[axis] - left[axis]);
+            let mid = [0, 1, 2].map(|i| left[i] + (right[i] - left[i]) * t);
+            let mid_hue = hue_of(vc, mid);
+            if are_in_cyclic_order(left_hue, target_hue, mid_hue) {
+                right = mid;
+                r_plane = m_plane;
+            } else {
+                left = mid;
+                left_hue = mid_hue;
+                l_plane = m_plane;
+            }
+        }
+    }
+    [0, 1, 2].map(|i| (left[i] + right[i]) / 2.)
+}
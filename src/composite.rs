@@ -0,0 +1,298 @@
+use crate::gamut::RgbColorSpace;
+use crate::{LinearSrgb, Srgb};
+
+/// A Porter-Duff compositing operator.
+///
+/// <https://www.w3.org/TR/compositing-1/#porterduffcompositingoperators>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompositeOperator {
+    /// `clear`: neither color is shown.
+    Clear,
+    /// `copy`: only the source is shown.
+    Copy,
+    /// `destination`: only the backdrop is shown.
+    Destination,
+    /// `source-over`, the default: the source is placed over the backdrop.
+    SourceOver,
+    /// `destination-over`: the backdrop is placed over the source.
+    DestinationOver,
+    /// `source-in`: the source where the backdrop is.
+    SourceIn,
+    /// `destination-in`: the backdrop where the source is.
+    DestinationIn,
+    /// `source-out`: the source where the backdrop is not.
+    SourceOut,
+    /// `destination-out`: the backdrop where the source is not.
+    DestinationOut,
+    /// `source-atop`: the source over the backdrop, only where the backdrop is.
+    SourceAtop,
+    /// `destination-atop`: the backdrop over the source, only where the source is.
+    DestinationAtop,
+    /// `xor`: the parts of each color that do not overlap.
+    Xor,
+    /// `lighter`: the sum of both colors.
+    Lighter,
+}
+
+impl Default for CompositeOperator {
+    fn default() -> Self {
+        CompositeOperator::SourceOver
+    }
+}
+
+/// A blend mode, as used by the CSS `mix-blend-mode` and `background-blend-mode` properties.
+///
+/// <https://www.w3.org/TR/compositing-1/#blending>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlendMode {
+    /// `normal`, the default: the source color.
+    Normal,
+    /// `multiply`.
+    Multiply,
+    /// `screen`.
+    Screen,
+    /// `overlay`.
+    Overlay,
+    /// `darken`.
+    Darken,
+    /// `lighten`.
+    Lighten,
+    /// `color-dodge`.
+    ColorDodge,
+    /// `color-burn`.
+    ColorBurn,
+    /// `hard-light`.
+    HardLight,
+    /// `soft-light`.
+    SoftLight,
+    /// `difference`.
+    Difference,
+    /// `exclusion`.
+    Exclusion,
+    /// `hue`: the hue of the source with the saturation and luminosity of the backdrop.
+    Hue,
+    /// `saturation`: the saturation of the source with the hue and luminosity of the backdrop.
+    Saturation,
+    /// `color`: the hue and saturation of the source with the luminosity of the backdrop.
+    Color,
+    /// `luminosity`: the luminosity of the source with the hue and saturation of the backdrop.
+    Luminosity,
+}
+
+impl Default for BlendMode {
+    fn default() -> Self {
+        BlendMode::Normal
+    }
+}
+
+/// The color space in which colors are blended, composited and filtered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlendSpace {
+    /// Gamma-encoded sRGB, as browsers do.
+    Srgb,
+    /// Linear-light sRGB, which is physically correct.
+    SrgbLinear,
+}
+
+impl Default for BlendSpace {
+    fn default() -> Self {
+        BlendSpace::Srgb
+    }
+}
+
+impl Srgb {
+    /// Composites this color as the source onto a backdrop color with a Porter-Duff operator.
+    ///
+    /// Both colors are clipped to the sRGB gamut first. If the result is fully transparent, it is
+    /// transparent black.
+    ///
+    /// <https://www.w3.org/TR/compositing-1/#advancedcompositing>
+    pub fn composite(
+        &self,
+        backdrop: Srgb,
+        operator: CompositeOperator,
+        space: BlendSpace,
+    ) -> Srgb {
+        self.blend_composite(backdrop, BlendMode::Normal, operator, space)
+    }
+
+    /// Blends this color as the source with a backdrop color, then composites the result over
+    /// the backdrop, as `mix-blend-mode` does.
+    ///
+    /// Both colors are clipped to the sRGB gamut first. Where the backdrop is translucent, the
+    /// blended color is mixed with the source color in proportion.
+    ///
+    /// <https://www.w3.org/TR/compositing-1/#generalformula>
+    pub fn blend(&self, backdrop: Srgb, mode: BlendMode, space: BlendSpace) -> Srgb {
+        self.blend_composite(backdrop, mode, CompositeOperator::SourceOver, space)
+    }
+
+    fn blend_composite(
+        &self,
+        backdrop: Srgb,
+        mode: BlendMode,
+        operator: CompositeOperator,
+        space: BlendSpace,
+    ) -> Srgb {
+        let (source, backdrop) = (self.clip(), backdrop.clip());
+        let (cs, cb) = match space {
+            BlendSpace::Srgb => (source.rgb(), backdrop.rgb()),
+            BlendSpace::SrgbLinear => (
+                LinearSrgb::from(source).rgb(),
+                LinearSrgb::from(backdrop).rgb(),
+            ),
+        };
+        let (a_s, a_b) = (source.alpha, backdrop.alpha);
+
+        let blended = blend(mode, cb, cs);
+        let (fa, fb) = operator.fractions(a_s, a_b);
+        let alpha = (a_s * fa + a_b * fb).min(1.);
+        if alpha == 0. {
+            return Srgb::new(0., 0., 0., 0.);
+        }
+        let mut rgb = [0.; 3];
+        for (i, component) in rgb.iter_mut().enumerate() {
+            let source = (1. - a_b) * cs[i] + a_b * blended[i];
+            let premultiplied = a_s * fa * source + a_b * fb * cb[i];
+            *component = (premultiplied / alpha).min(1.);
+        }
+
+        let [r, g, b] = rgb;
+        match space {
+            BlendSpace::Srgb => Srgb::new(r, g, b, alpha),
+            BlendSpace::SrgbLinear => Srgb::from(LinearSrgb::new(r, g, b, alpha)),
+        }
+    }
+}
+
+impl CompositeOperator {
+    /// Returns the fractions of the source and backdrop in the result.
+    fn fractions(self, a_s: f32, a_b: f32) -> (f32, f32) {
+        match self {
+            CompositeOperator::Clear => (0., 0.),
+            CompositeOperator::Copy => (1., 0.),
+            CompositeOperator::Destination => (0., 1.),
+            CompositeOperator::SourceOver => (1., 1. - a_s),
+            CompositeOperator::DestinationOver => (1. - a_b, 1.),
+            CompositeOperator::SourceIn => (a_b, 0.),
+            CompositeOperator::DestinationIn => (0., a_s),
+            CompositeOperator::SourceOut => (1. - a_b, 0.),
+            CompositeOperator::DestinationOut => (0., 1. - a_s),
+            CompositeOperator::SourceAtop => (a_b, 1. - a_s),
+            CompositeOperator::DestinationAtop => (1. - a_b, a_s),
+            CompositeOperator::Xor => (1. - a_b, 1. - a_s),
+            CompositeOperator::Lighter => (1., 1.),
+        }
+    }
+}
+
+/// Returns the result of blending a source color with a backdrop color.
+fn blend(mode: BlendMode, cb: [f32; 3], cs: [f32; 3]) -> [f32; 3] {
+    let separable = |f: fn(f32, f32) -> f32| [f(cb[0], cs[0]), f(cb[1], cs[1]), f(cb[2], cs[2])];
+    match mode {
+        BlendMode::Normal => cs,
+        BlendMode::Multiply => separable(multiply),
+        BlendMode::Screen => separable(screen),
+        BlendMode::Overlay => separable(|cb, cs| hard_light(cs, cb)),
+        BlendMode::Darken => separable(f32::min),
+        BlendMode::Lighten => separable(f32::max),
+        BlendMode::ColorDodge => separable(color_dodge),
+        BlendMode::ColorBurn => separable(color_burn),
+        BlendMode::HardLight => separable(hard_light),
+        BlendMode::SoftLight => separable(soft_light),
+        BlendMode::Difference => separable(|cb, cs| (cb - cs).abs()),
+        BlendMode::Exclusion => separable(|cb, cs| cb + cs - 2. * cb * cs),
+        BlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+        BlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+        BlendMode::Color => set_lum(cs, lum(cb)),
+        BlendMode::Luminosity => set_lum(cb, lum(cs)),
+    }
+}
+
+fn multiply(cb: f32, cs: f32) -> f32 {
+    cb * cs
+}
+
+fn screen(cb: f32, cs: f32) -> f32 {
+    cb + cs - cb * cs
+}
+
+fn color_dodge(cb: f32, cs: f32) -> f32 {
+    if cb == 0. {
+        0.
+    } else if cs == 1. {
+        1.
+    } else {
+        (cb / (1. - cs)).min(1.)
+    }
+}
+
+fn color_burn(cb: f32, cs: f32) -> f32 {
+    if cb == 1. {
+        1.
+    } else if cs == 0. {
+        0.
+    } else {
+        1. - ((1. - cb) / cs).min(1.)
+    }
+}
+
+fn hard_light(cb: f32, cs: f32) -> f32 {
+    if cs <= 0.5 {
+        multiply(cb, 2. * cs)
+    } else {
+        screen(cb, 2. * cs - 1.)
+    }
+}
+
+fn soft_light(cb: f32, cs: f32) -> f32 {
+    if cs <= 0.5 {
+        cb - (1. - 2. * cs) * cb * (1. - cb)
+    } else {
+        let d = if cb <= 0.25 {
+            ((16. * cb - 12.) * cb + 4.) * cb
+        } else {
+            cb.sqrt()
+        };
+        cb + (2. * cs - 1.) * (d - cb)
+    }
+}
+
+// https://www.w3.org/TR/compositing-1/#blendingnonseparable
+fn lum([r, g, b]: [f32; 3]) -> f32 {
+    0.3 * r + 0.59 * g + 0.11 * b
+}
+
+fn clip_color(c: [f32; 3]) -> [f32; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    c.map(|c| {
+        let mut c = c;
+        if n < 0. {
+            c = l + (c - l) * l / (l - n);
+        }
+        if x > 1. {
+            c = l + (c - l) * (1. - l) / (x - l);
+        }
+        c
+    })
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    clip_color(c.map(|c| c + d))
+}
+
+fn sat([r, g, b]: [f32; 3]) -> f32 {
+    r.max(g).max(b) - r.min(g).min(b)
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let max = c[0].max(c[1]).max(c[2]);
+    let min = c[0].min(c[1]).min(c[2]);
+    if max == min {
+        return [0.; 3];
+    }
+    c.map(|c| (c - min) * s / (max - min))
+}
@@ -0,0 +1,261 @@
+use crate::{ColorSpace, LinearSrgb, Oklch, Srgb};
+use std::cmp::Ordering;
+
+impl Srgb {
+    /// Returns the relative luminance as defined by WCAG 2.x, from 0 for black to 1 for white.
+    ///
+    /// The alpha component is ignored.
+    ///
+    /// <https://www.w3.org/TR/WCAG22/#dfn-relative-luminance>
+    pub fn relative_luminance(&self) -> f32 {
+        let linear = LinearSrgb::from(*self);
+        0.2126 * linear.red + 0.7152 * linear.green + 0.0722 * linear.blue
+    }
+}
+
+/// Returns the WCAG 2.x contrast ratio between a foreground and background color, from 1 to 21.
+///
+/// A semi-transparent foreground is composited onto the background first. The background is
+/// treated as opaque.
+///
+/// <https://www.w3.org/TR/WCAG22/#dfn-contrast-ratio>
+pub fn contrast_ratio(foreground: Srgb, background: Srgb) -> f32 {
+    let foreground = over(foreground, background);
+    let l1 = f64::from(foreground.relative_luminance());
+    let l2 = f64::from(background.relative_luminance());
+    ((l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)) as f32
+}
+
+/// Composites a color onto an opaque background in gamma-encoded sRGB, as browsers do.
+fn over(foreground: Srgb, background: Srgb) -> Srgb {
+    let alpha = foreground.alpha;
+    let blend = |f: f32, b: f32| f * alpha + b * (1. - alpha);
+    Srgb::new(
+        blend(foreground.red, background.red),
+        blend(foreground.green, background.green),
+        blend(foreground.blue, background.blue),
+        1.,
+    )
+}
+
+/// The size of text for WCAG contrast requirements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextSize {
+    /// Text smaller than large text.
+    Normal,
+    /// Text of at least 18 points, or 14 points if bold.
+    Large,
+}
+
+/// The WCAG 2.x conformance level met by a contrast ratio.
+///
+/// Levels are ordered, so `level >= WcagLevel::AA` checks for at least AA.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WcagLevel {
+    /// Below the AA minimum.
+    Fail,
+    /// Meets the minimum contrast of success criterion 1.4.3.
+    AA,
+    /// Meets the enhanced contrast of success criterion 1.4.6.
+    AAA,
+}
+
+impl WcagLevel {
+    /// Classifies a contrast ratio for text of the given size.
+    ///
+    /// <https://www.w3.org/TR/WCAG22/#contrast-minimum>
+    pub fn from_ratio(ratio: f32, size: TextSize) -> WcagLevel {
+        let (aa, aaa) = match size {
+            TextSize::Normal => (4.5, 7.),
+            TextSize::Large => (3., 4.5),
+        };
+        if ratio >= aaa {
+            WcagLevel::AAA
+        } else if ratio >= aa {
+            WcagLevel::AA
+        } else {
+            WcagLevel::Fail
+        }
+    }
+}
+
+/// Returns the APCA lightness contrast (Lc) of text on a background, from about -108 to 106.
+///
+/// The result is positive for dark text on a light background and negative for light text on a
+/// dark background. Its magnitude is what guidelines refer to, for example Lc 75 for body text.
+/// A semi-transparent text color is composited onto the background first. APCA is part of the
+/// WCAG 3 working draft; this is APCA-W3 version 0.0.98G-4g.
+///
+/// <https://github.com/Myndex/apca-w3>
+pub fn apca_contrast(text: Srgb, background: Srgb) -> f32 {
+    const NORM_BG: f64 = 0.56;
+    const NORM_TXT: f64 = 0.57;
+    const REV_TXT: f64 = 0.62;
+    const REV_BG: f64 = 0.65;
+    const SCALE_BOW: f64 = 1.14;
+    const SCALE_WOB: f64 = 1.14;
+    const LO_BOW_OFFSET: f64 = 0.027;
+    const LO_WOB_OFFSET: f64 = 0.027;
+    const DELTA_Y_MIN: f64 = 0.0005;
+    const LO_CLIP: f64 = 0.1;
+
+    let text = apca_luminance(over(text, background));
+    let background = apca_luminance(background);
+    if (background - text).abs() < DELTA_Y_MIN {
+        return 0.;
+    }
+    let contrast = if background > text {
+        // Dark text on a light background.
+        let sapc = (background.powf(NORM_BG) - text.powf(NORM_TXT)) * SCALE_BOW;
+        if sapc < LO_CLIP {
+            0.
+        } else {
+            sapc - LO_BOW_OFFSET
+        }
+    } else {
+        // Light text on a dark background.
+        let sapc = (background.powf(REV_BG) - text.powf(REV_TXT)) * SCALE_WOB;
+        if sapc > -LO_CLIP {
+            0.
+        } else {
+            sapc + LO_WOB_OFFSET
+        }
+    };
+    (contrast * 100.) as f32
+}
+
+/// Returns the screen luminance estimate used by APCA, with soft clamping of near blacks.
+fn apca_luminance(color: Srgb) -> f64 {
+    const MAIN_TRC: f64 = 2.4;
+    const BLACK_THRESHOLD: f64 = 0.022;
+    const BLACK_CLAMP: f64 = 1.414;
+
+    let channel = |value: f32| f64::from(value.clamp(0., 1.)).powf(MAIN_TRC);
+    let y = 0.2126729 * channel(color.red)
+        + 0.7151522 * channel(color.green)
+        + 0.0721750 * channel(color.blue);
+    if y > BLACK_THRESHOLD {
+        y
+    } else {
+        y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+    }
+}
+
+/// Returns the minimum font size in CSS pixels for text of the given weight at an APCA
+/// lightness contrast, or `None` if the contrast is too low for text.
+///
+/// The polarity of the contrast is ignored. Weights are rounded to the nearest hundred, and
+/// contrasts between rows of the APCA-W3 font lookup table use the lower row.
+///
+/// <https://github.com/Myndex/apca-w3>
+pub fn apca_font_size(contrast: f32, weight: u16) -> Option<f32> {
+    let column = (usize::from(weight.clamp(100, 900)) + 50) / 100 - 1;
+    let contrast = contrast.abs();
+    let row = APCA_FONT_SIZES
+        .iter()
+        .rev()
+        .find(|(lc, _)| contrast >= *lc)?;
+    let size = row.1[column];
+    if size >= NON_TEXT {
+        None
+    } else {
+        Some(size)
+    }
+}
+
+/// Sizes at or above this value in the lookup table mean that text is not allowed.
+const NON_TEXT: f32 = 777.;
+
+/// The APCA-W3 font lookup table: minimum font sizes for weights 100 to 900 by lightness contrast.
+static APCA_FONT_SIZES: [(f32, [f32; 9]); 25] = [
+    (0., [999., 999., 999., 999., 999., 999., 999., 999., 999.]),
+    (10., [999., 999., 999., 999., 999., 999., 999., 999., 999.]),
+    (15., [777., 777., 777., 777., 777., 777., 777., 777., 777.]),
+    (20., [777., 777., 777., 777., 777., 777., 777., 777., 777.]),
+    (25., [777., 777., 777., 120., 120., 108., 96., 96., 96.]),
+    (30., [777., 777., 120., 108., 108., 96., 72., 72., 72.]),
+    (35., [777., 120., 108., 96., 72., 60., 48., 48., 48.]),
+    (40., [120., 108., 96., 60., 48., 42., 32., 32., 32.]),
+    (45., [108., 96., 72., 42., 32., 28., 24., 24., 24.]),
+    (50., [96., 72., 60., 32., 28., 24., 21., 21., 21.]),
+    (55., [80., 60., 48., 28., 24., 21., 18., 18., 18.]),
+    (60., [72., 48., 42., 24., 21., 18., 16., 16., 18.]),
+    (65., [68., 46., 32., 21.75, 19., 17., 15., 16., 18.]),
+    (70., [64., 44., 28., 19.5, 18., 16., 14.5, 16., 18.]),
+    (75., [60., 42., 24., 18., 16., 15., 14., 16., 18.]),
+    (80., [56., 38.25, 23., 17.25, 15.81, 14.81, 14., 16., 18.]),
+    (85., [52., 34.5, 22., 16.5, 15.625, 14.625, 14., 16., 18.]),
+    (90., [48., 32., 21., 16., 15.5, 14.5, 14., 16., 18.]),
+    (95., [45., 28., 19.5, 15.5, 15., 14., 13.5, 16., 18.]),
+    (100., [42., 26.5, 18.5, 15., 14.5, 13.5, 13., 16., 18.]),
+    (105., [39., 25., 18., 14.5, 14., 13., 12., 16., 18.]),
+    (110., [36., 24., 18., 14., 13., 12., 11., 16., 18.]),
+    (115., [34., 22.5, 17.5, 13.5, 12.5, 11.5, 10., 16., 18.]),
+    (120., [32., 21., 17., 13., 12., 11., 10., 16., 18.]),
+    (125., [30., 20., 16.5, 12.5, 11.5, 10.5, 10., 16., 18.]),
+];
+
+/// A minimum contrast for [`Srgb::adjust_contrast`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ContrastTarget {
+    /// A WCAG 2.x contrast ratio, such as 4.5 for AA.
+    Wcag(f32),
+    /// An APCA lightness contrast. The polarity is ignored.
+    Apca(f32),
+}
+
+impl ContrastTarget {
+    fn is_met(self, foreground: Srgb, background: Srgb) -> bool {
+        match self {
+            ContrastTarget::Wcag(ratio) => contrast_ratio(foreground, background) >= ratio,
+            ContrastTarget::Apca(lc) => apca_contrast(foreground, background).abs() >= lc.abs(),
+        }
+    }
+}
+
+/// The number of binary search steps on Oklch lightness.
+const ADJUST_ITERATIONS: usize = 24;
+
+impl Srgb {
+    /// Returns the color closest to this one that meets the contrast target on the background, or
+    /// `None` if no color does.
+    ///
+    /// Only the Oklch lightness is changed, towards whichever of black or white needs the smaller
+    /// change. Hue and chroma are kept as far as the sRGB gamut allows. Colors that already meet
+    /// the target are returned unchanged.
+    pub fn adjust_contrast(&self, background: Srgb, target: ContrastTarget) -> Option<Srgb> {
+        if target.is_met(*self, background) {
+            return Some(*self);
+        }
+        let origin = self.convert::<Oklch>();
+        let color_at = |lightness: f32| {
+            let mut color = origin;
+            color.lightness = lightness;
+            color.to_gamut::<Srgb>()
+        };
+
+        [0., 1.]
+            .iter()
+            .filter(|&&extreme| target.is_met(color_at(extreme), background))
+            .map(|&extreme| {
+                // The lightness moves from `failing` towards `passing`.
+                let mut failing = origin.lightness;
+                let mut passing = extreme;
+                for _ in 0..ADJUST_ITERATIONS {
+                    let mid = (failing + passing) / 2.;
+                    if target.is_met(color_at(mid), background) {
+                        passing = mid;
+                    } else {
+                        failing = mid;
+                    }
+                }
+                passing
+            })
+            .min_by(|a, b| {
+                let da = (a - origin.lightness).abs();
+                let db = (b - origin.lightness).abs();
+                da.partial_cmp(&db).unwrap_or(Ordering::Equal)
+            })
+            .map(color_at)
+    }
+}
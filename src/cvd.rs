@@ -0,0 +1,124 @@
+use crate::gamut::RgbColorSpace;
+use crate::interpolate::lerp;
+use crate::xyz::{multiply, Matrix};
+use crate::{LinearSrgb, Srgb};
+
+/// A color vision deficiency.
+///
+/// Each deficiency has a severity from 0 for normal vision to 1 for the complete loss of a cone
+/// type. Severities in between model the anomalous forms, such as protanomaly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorVisionDeficiency {
+    /// Protanopia and protanomaly, affecting the long-wavelength (red) cones.
+    Protan,
+    /// Deuteranopia and deuteranomaly, affecting the medium-wavelength (green) cones.
+    Deutan,
+    /// Tritanopia and tritanomaly, affecting the short-wavelength (blue) cones.
+    Tritan,
+    /// Achromatopsia and achromatomaly, where colors are seen as shades of gray.
+    Achromat,
+}
+
+// Viénot, Brettel and Mollon, "Digital video colourmaps for checking the legibility of displays
+// by dichromats", 1999.
+const LINEAR_SRGB_TO_LMS: Matrix = [
+    [17.8824, 43.5161, 4.11935],
+    [3.45565, 27.1554, 3.86714],
+    [0.0299566, 0.184309, 1.46709],
+];
+
+const LMS_TO_LINEAR_SRGB: Matrix = [
+    [0.0809444479, -0.130504409, 0.116721066],
+    [-0.0102485335, 0.0540193266, -0.113614708],
+    [-0.000365296938, -0.00412161469, 0.693511405],
+];
+
+/// Projections of LMS colors onto the plane seen by protanopes and deuteranopes. The plane
+/// contains the neutral axis, so grays are unchanged.
+const PROTANOPIA: Matrix = [[0., 2.02344, -2.52581], [0., 1., 0.], [0., 0., 1.]];
+const DEUTERANOPIA: Matrix = [[1., 0., 0.], [0.494207, 0., 1.24827], [0., 0., 1.]];
+
+// Brettel, Viénot and Mollon, "Computerized simulation of color appearance for dichromats",
+// 1997, precomputed for linear sRGB. A single plane is a poor fit for tritanopes, so colors are
+// projected onto one of two half-planes depending on their side of the separation plane.
+// https://github.com/DaltonLens/libDaltonLens
+const TRITANOPIA: [Matrix; 2] = [
+    [
+        [1.01277, 0.13548, -0.14826],
+        [-0.01243, 0.86812, 0.14431],
+        [0.07589, 0.80500, 0.11911],
+    ],
+    [
+        [0.93678, 0.18979, -0.12657],
+        [0.06154, 0.81526, 0.12320],
+        [-0.37562, 1.12767, 0.24796],
+    ],
+];
+const TRITANOPIA_SEPARATION: [f32; 3] = [0.03901, -0.02788, -0.01113];
+
+impl Srgb {
+    /// Returns how this color appears with a color vision deficiency of the given severity, from
+    /// 0 to 1.
+    ///
+    /// Dichromacy is simulated in linear sRGB with the model of Viénot et al. for protans and
+    /// deutans, and of Brettel et al. for tritans. Achromatopsia keeps only the luminance. Lower
+    /// severities interpolate linearly towards the original color. The result is clipped to the
+    /// sRGB gamut.
+    ///
+    /// <https://daltonlens.org/understanding-cvd-simulation/>
+    pub fn simulate_cvd(&self, deficiency: ColorVisionDeficiency, severity: f32) -> Srgb {
+        let linear = LinearSrgb::from(*self);
+        let rgb = [linear.red, linear.green, linear.blue];
+        let [r, g, b] = simulate(rgb, deficiency, severity);
+        Srgb::from(LinearSrgb::new(r, g, b, linear.alpha)).clip()
+    }
+
+    /// Returns this color corrected so that details lost to a color vision deficiency are more
+    /// visible.
+    ///
+    /// The difference between the color and its simulation is shifted into the channels the
+    /// viewer can still tell apart, following Fidaner et al. The correction is computed in
+    /// linear sRGB and the result is clipped to the sRGB gamut.
+    pub fn daltonize(&self, deficiency: ColorVisionDeficiency, severity: f32) -> Srgb {
+        let linear = LinearSrgb::from(*self);
+        let rgb = [linear.red, linear.green, linear.blue];
+        let simulated = simulate(rgb, deficiency, severity);
+        let error = [
+            rgb[0] - simulated[0],
+            rgb[1] - simulated[1],
+            rgb[2] - simulated[2],
+        ];
+        let r = rgb[0];
+        let g = rgb[1] + 0.7 * error[0] + error[1];
+        let b = rgb[2] + 0.7 * error[0] + error[2];
+        Srgb::from(LinearSrgb::new(r, g, b, linear.alpha)).clip()
+    }
+}
+
+fn simulate(rgb: [f32; 3], deficiency: ColorVisionDeficiency, severity: f32) -> [f32; 3] {
+    let [r, g, b] = rgb;
+    let dichromat = |projection: &Matrix| {
+        let lms = multiply(&LINEAR_SRGB_TO_LMS, rgb);
+        multiply(&LMS_TO_LINEAR_SRGB, multiply(projection, lms))
+    };
+    let simulated = match deficiency {
+        ColorVisionDeficiency::Protan => dichromat(&PROTANOPIA),
+        ColorVisionDeficiency::Deutan => dichromat(&DEUTERANOPIA),
+        ColorVisionDeficiency::Tritan => {
+            let [nr, ng, nb] = TRITANOPIA_SEPARATION;
+            let side = usize::from(r * nr + g * ng + b * nb < 0.);
+            multiply(&TRITANOPIA[side], rgb)
+        }
+        ColorVisionDeficiency::Achromat => [0.2126 * r + 0.7152 * g + 0.0722 * b; 3],
+    };
+    blend(rgb, simulated, severity)
+}
+
+fn blend(original: [f32; 3], simulated: [f32; 3], severity: f32) -> [f32; 3] {
+    let severity = severity.clamp(0., 1.);
+    [
+        lerp(original[0], simulated[0], severity),
+        lerp(original[1], simulated[1], severity),
+        lerp(original[2], simulated[2], severity),
+    ]
+}
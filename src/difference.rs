@@ -0,0 +1,166 @@
+use crate::space::ColorSpace;
+use crate::{Lab, Oklab};
+
+/// A color difference formula.
+///
+/// Differences are computed from CIE Lab with a D50 white point, except for [`DeltaE::Ok`], which
+/// uses Oklab. Alpha is ignored.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeltaE {
+    /// ΔE*76, the Euclidean distance in Lab.
+    Cie76,
+    /// ΔE*94 with the weights for graphic arts.
+    Cie94,
+    /// CIEDE2000.
+    ///
+    /// <http://www2.ece.rochester.edu/~gsharma/ciede2000/>
+    Ciede2000,
+    /// CMC l:c. The difference is relative to the first color. Common weights are 2:1 for
+    /// acceptability and 1:1 for perceptibility.
+    Cmc {
+        /// The lightness weight.
+        lightness: f32,
+        /// The chroma weight.
+        chroma: f32,
+    },
+    /// ΔEOK, the Euclidean distance in Oklab.
+    ///
+    /// <https://www.w3.org/TR/css-color-4/#color-difference-OK>
+    Ok,
+}
+
+/// Returns the difference between two colors.
+pub(crate) fn delta_e<S: ColorSpace, T: ColorSpace>(
+    reference: S,
+    sample: T,
+    method: DeltaE,
+) -> f32 {
+    if method == DeltaE::Ok {
+        return delta_eok(reference.convert(), sample.convert());
+    }
+    let lab = |lab: Lab| [lab.lightness, lab.a, lab.b].map(f64::from);
+    let reference = lab(reference.convert());
+    let sample = lab(sample.convert());
+    let difference = match method {
+        DeltaE::Cie76 => cie76(reference, sample),
+        DeltaE::Cie94 => cie94(reference, sample),
+        DeltaE::Ciede2000 => ciede2000(reference, sample),
+        DeltaE::Cmc { lightness, chroma } => {
+            cmc(reference, sample, f64::from(lightness), f64::from(chroma))
+        }
+        DeltaE::Ok => unreachable!(),
+    };
+    difference as f32
+}
+
+// https://www.w3.org/TR/css-color-4/#color-difference-OK
+pub(crate) fn delta_eok(lhs: Oklab, rhs: Oklab) -> f32 {
+    let dl = lhs.lightness - rhs.lightness;
+    let da = lhs.a - rhs.a;
+    let db = lhs.b - rhs.b;
+    (dl * dl + da * da + db * db).sqrt()
+}
+
+fn cie76([l1, a1, b1]: [f64; 3], [l2, a2, b2]: [f64; 3]) -> f64 {
+    ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+}
+
+/// Returns the differences in lightness, chroma and hue, with the hue difference squared.
+fn lch_differences([l1, a1, b1]: [f64; 3], [l2, a2, b2]: [f64; 3]) -> (f64, f64, f64) {
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+    let dc = c1 - c2;
+    let dh2 = ((a1 - a2).powi(2) + (b1 - b2).powi(2) - dc * dc).max(0.);
+    (l1 - l2, dc, dh2)
+}
+
+// http://www.brucelindbloom.com/index.html?Eqn_DeltaE_CIE94.html
+fn cie94(reference: [f64; 3], sample: [f64; 3]) -> f64 {
+    const K1: f64 = 0.045;
+    const K2: f64 = 0.015;
+    let c1 = reference[1].hypot(reference[2]);
+    let (dl, dc, dh2) = lch_differences(reference, sample);
+    let sc = 1. + K1 * c1;
+    let sh = 1. + K2 * c1;
+    (dl * dl + (dc / sc).powi(2) + dh2 / (sh * sh)).sqrt()
+}
+
+// http://www.brucelindbloom.com/index.html?Eqn_DeltaE_CMC.html
+fn cmc(reference: [f64; 3], sample: [f64; 3], l: f64, c: f64) -> f64 {
+    let [l1, a1, b1] = reference;
+    let c1 = a1.hypot(b1);
+    let h1 = b1.atan2(a1).to_degrees().rem_euclid(360.);
+    let (dl, dc, dh2) = lch_differences(reference, sample);
+
+    let sl = if l1 < 16. {
+        0.511
+    } else {
+        0.040975 * l1 / (1. + 0.01765 * l1)
+    };
+    let sc = 0.0638 * c1 / (1. + 0.0131 * c1) + 0.638;
+    let f = (c1.powi(4) / (c1.powi(4) + 1900.)).sqrt();
+    let t = if (164. ..=345.).contains(&h1) {
+        0.56 + (0.2 * (h1 + 168.).to_radians().cos()).abs()
+    } else {
+        0.36 + (0.4 * (h1 + 35.).to_radians().cos()).abs()
+    };
+    let sh = sc * (f * t + 1. - f);
+    ((dl / (l * sl)).powi(2) + (dc / (c * sc)).powi(2) + dh2 / (sh * sh)).sqrt()
+}
+
+// http://www2.ece.rochester.edu/~gsharma/ciede2000/ciede2000noteCRNA.pdf
+fn ciede2000([l1, a1, b1]: [f64; 3], [l2, a2, b2]: [f64; 3]) -> f64 {
+    let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.;
+    let g = 0.5 * (1. - (c_mean.powi(7) / (c_mean.powi(7) + 25_f64.powi(7))).sqrt());
+    let a1 = a1 * (1. + g);
+    let a2 = a2 * (1. + g);
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+    let hue = |a: f64, b: f64| {
+        if a == 0. && b == 0. {
+            0.
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.)
+        }
+    };
+    let h1 = hue(a1, b1);
+    let h2 = hue(a2, b2);
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0. {
+        0.
+    } else if (h2 - h1).abs() <= 180. {
+        h2 - h1
+    } else if h2 - h1 > 180. {
+        h2 - h1 - 360.
+    } else {
+        h2 - h1 + 360.
+    };
+    let dh = 2. * (c1 * c2).sqrt() * (dh / 2.).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.;
+    let c_mean = (c1 + c2) / 2.;
+    let h_mean = if c1 * c2 == 0. {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180. {
+        (h1 + h2) / 2.
+    } else if h1 + h2 < 360. {
+        (h1 + h2 + 360.) / 2.
+    } else {
+        (h1 + h2 - 360.) / 2.
+    };
+
+    let t = 1. - 0.17 * (h_mean - 30.).to_radians().cos()
+        + 0.24 * (2. * h_mean).to_radians().cos()
+        + 0.32 * (3. * h_mean + 6.).to_radians().cos()
+        - 0.20 * (4. * h_mean - 63.).to_radians().cos();
+    let d_theta = 30. * (-((h_mean - 275.) / 25.).powi(2)).exp();
+    let rc = 2. * (c_mean.powi(7) / (c_mean.powi(7) + 25_f64.powi(7))).sqrt();
+    let sl = 1. + 0.015 * (l_mean - 50.).powi(2) / (20. + (l_mean - 50.).powi(2)).sqrt();
+    let sc = 1. + 0.045 * c_mean;
+    let sh = 1. + 0.015 * c_mean * t;
+    let rt = -(2. * d_theta).to_radians().sin() * rc;
+
+    ((dl / sl).powi(2) + (dc / sc).powi(2) + (dh / sh).powi(2) + rt * (dc / sc) * (dh / sh)).sqrt()
+}
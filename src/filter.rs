@@ -0,0 +1,284 @@
+use crate::gamut::RgbColorSpace;
+use crate::{
+    consume_byte, consume_function, consume_none, is_ident_start, parse_hue, parse_number, skip_ws,
+    BlendSpace, LinearSrgb, ParseColorError, Srgb,
+};
+use std::str::FromStr;
+
+/// A CSS filter function that changes colors.
+///
+/// Functions that move pixels, such as `blur()` and `drop-shadow()`, are not supported.
+///
+/// <https://www.w3.org/TR/filter-effects-1/#filter-functions>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Filter {
+    /// `grayscale()`, from 0 for no change to 1 for fully gray. Larger values are clamped.
+    Grayscale(f32),
+    /// `sepia()`, from 0 for no change to 1 for fully sepia. Larger values are clamped.
+    Sepia(f32),
+    /// `saturate()`, where 0 is fully unsaturated, 1 is no change and larger values
+    /// oversaturate.
+    Saturate(f32),
+    /// `hue-rotate()`, an angle in degrees.
+    HueRotate(f32),
+    /// `invert()`, from 0 for no change to 1 for fully inverted. Larger values are clamped.
+    Invert(f32),
+    /// `opacity()`, from 0 for fully transparent to 1 for no change. Larger values are clamped.
+    Opacity(f32),
+    /// `brightness()`, where 0 is black, 1 is no change and larger values brighten.
+    Brightness(f32),
+    /// `contrast()`, where 0 is gray, 1 is no change and larger values add contrast.
+    Contrast(f32),
+    /// An `feColorMatrix` of type `matrix`: 4 rows of 5 values in row-major order. Each row
+    /// computes the red, green, blue or alpha output from the red, green, blue and alpha inputs
+    /// plus an offset.
+    ///
+    /// <https://www.w3.org/TR/filter-effects-1/#feColorMatrixElement>
+    ColorMatrix([f32; 20]),
+}
+
+impl Filter {
+    /// Parses a space-separated list of filter functions, or `none` for an empty list.
+    pub fn parse_list(s: &str) -> Result<Vec<Filter>, ParseColorError> {
+        parse_filter_list(s.as_bytes()).map_err(|_| ParseColorError)
+    }
+
+    /// Returns the color matrix equivalent to the filter, in the format of
+    /// [`Filter::ColorMatrix`].
+    ///
+    /// <https://www.w3.org/TR/filter-effects-1/#ShorthandEquivalents>
+    pub fn matrix(&self) -> [f32; 20] {
+        match *self {
+            Filter::Grayscale(amount) => {
+                let s = 1. - amount.clamp(0., 1.);
+                #[rustfmt::skip]
+                let matrix = [
+                    0.2126 + 0.7874 * s, 0.7152 - 0.7152 * s, 0.0722 - 0.0722 * s, 0., 0.,
+                    0.2126 - 0.2126 * s, 0.7152 + 0.2848 * s, 0.0722 - 0.0722 * s, 0., 0.,
+                    0.2126 - 0.2126 * s, 0.7152 - 0.7152 * s, 0.0722 + 0.9278 * s, 0., 0.,
+                    0., 0., 0., 1., 0.,
+                ];
+                matrix
+            }
+            Filter::Sepia(amount) => {
+                let s = 1. - amount.clamp(0., 1.);
+                #[rustfmt::skip]
+                let matrix = [
+                    0.393 + 0.607 * s, 0.769 - 0.769 * s, 0.189 - 0.189 * s, 0., 0.,
+                    0.349 - 0.349 * s, 0.686 + 0.314 * s, 0.168 - 0.168 * s, 0., 0.,
+                    0.272 - 0.272 * s, 0.534 - 0.534 * s, 0.131 + 0.869 * s, 0., 0.,
+                    0., 0., 0., 1., 0.,
+                ];
+                matrix
+            }
+            Filter::Saturate(s) => {
+                #[rustfmt::skip]
+                let matrix = [
+                    0.213 + 0.787 * s, 0.715 - 0.715 * s, 0.072 - 0.072 * s, 0., 0.,
+                    0.213 - 0.213 * s, 0.715 + 0.285 * s, 0.072 - 0.072 * s, 0., 0.,
+                    0.213 - 0.213 * s, 0.715 - 0.715 * s, 0.072 + 0.928 * s, 0., 0.,
+                    0., 0., 0., 1., 0.,
+                ];
+                matrix
+            }
+            Filter::HueRotate(degrees) => {
+                let (sin, cos) = degrees.to_radians().sin_cos();
+                #[rustfmt::skip]
+                let matrix = [
+                    0.213 + 0.787 * cos - 0.213 * sin,
+                    0.715 - 0.715 * cos - 0.715 * sin,
+                    0.072 - 0.072 * cos + 0.928 * sin,
+                    0., 0.,
+                    0.213 - 0.213 * cos + 0.143 * sin,
+                    0.715 + 0.285 * cos + 0.140 * sin,
+                    0.072 - 0.072 * cos - 0.283 * sin,
+                    0., 0.,
+                    0.213 - 0.213 * cos - 0.787 * sin,
+                    0.715 - 0.715 * cos + 0.715 * sin,
+                    0.072 + 0.928 * cos + 0.072 * sin,
+                    0., 0.,
+                    0., 0., 0., 1., 0.,
+                ];
+                matrix
+            }
+            Filter::Invert(amount) => {
+                let a = amount.clamp(0., 1.);
+                let slope = 1. - 2. * a;
+                #[rustfmt::skip]
+                let matrix = [
+                    slope, 0., 0., 0., a,
+                    0., slope, 0., 0., a,
+                    0., 0., slope, 0., a,
+                    0., 0., 0., 1., 0.,
+                ];
+                matrix
+            }
+            Filter::Opacity(amount) => {
+                let a = amount.clamp(0., 1.);
+                #[rustfmt::skip]
+                let matrix = [
+                    1., 0., 0., 0., 0.,
+                    0., 1., 0., 0., 0.,
+                    0., 0., 1., 0., 0.,
+                    0., 0., 0., a, 0.,
+                ];
+                matrix
+            }
+            Filter::Brightness(a) => {
+                #[rustfmt::skip]
+                let matrix = [
+                    a, 0., 0., 0., 0.,
+                    0., a, 0., 0., 0.,
+                    0., 0., a, 0., 0.,
+                    0., 0., 0., 1., 0.,
+                ];
+                matrix
+            }
+            Filter::Contrast(a) => {
+                let intercept = 0.5 - 0.5 * a;
+                #[rustfmt::skip]
+                let matrix = [
+                    a, 0., 0., 0., intercept,
+                    0., a, 0., 0., intercept,
+                    0., 0., a, 0., intercept,
+                    0., 0., 0., 1., 0.,
+                ];
+                matrix
+            }
+            Filter::ColorMatrix(matrix) => matrix,
+        }
+    }
+
+    /// Applies the filter to a color in the given color space.
+    ///
+    /// The color is clipped to the sRGB gamut and the filter operates on straight alpha. CSS
+    /// filter functions are applied in gamma-encoded sRGB by browsers, while SVG filters such as
+    /// `feColorMatrix` default to linear-light sRGB (`color-interpolation-filters: linearRGB`).
+    /// The result is clamped to the sRGB gamut.
+    pub fn apply(&self, color: Srgb, space: BlendSpace) -> Srgb {
+        let m = self.matrix();
+        let color = color.clip();
+        let rgb = match space {
+            BlendSpace::Srgb => color.rgb(),
+            BlendSpace::SrgbLinear => LinearSrgb::from(color).rgb(),
+        };
+        let input = [rgb[0], rgb[1], rgb[2], color.alpha.clamp(0., 1.)];
+        let row = |r: usize| {
+            let m = &m[r * 5..r * 5 + 5];
+            let value = m[0] * input[0] + m[1] * input[1] + m[2] * input[2] + m[3] * input[3];
+            (value + m[4]).clamp(0., 1.)
+        };
+        match space {
+            BlendSpace::Srgb => Srgb::new(row(0), row(1), row(2), row(3)),
+            BlendSpace::SrgbLinear => Srgb::from(LinearSrgb::new(row(0), row(1), row(2), row(3))),
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_filter(s.as_bytes()) {
+            Ok((input, filter)) if skip_ws(input).is_empty() => Ok(filter),
+            _ => Err(ParseColorError),
+        }
+    }
+}
+
+impl Srgb {
+    /// Applies a list of filters to the color, in order, in the given color space.
+    pub fn filter(&self, filters: &[Filter], space: BlendSpace) -> Srgb {
+        filters
+            .iter()
+            .fold(*self, |color, filter| filter.apply(color, space))
+    }
+}
+
+fn parse_filter_list(input: &[u8]) -> Result<Vec<Filter>, ()> {
+    let mut input = skip_ws(input);
+    if let Ok(rest) = consume_none(input) {
+        return if skip_ws(rest).is_empty() {
+            Ok(Vec::new())
+        } else {
+            Err(())
+        };
+    }
+    let mut filters = Vec::new();
+    while !input.is_empty() {
+        let (rest, filter) = parse_filter(input)?;
+        filters.push(filter);
+        input = skip_ws(rest);
+    }
+    if filters.is_empty() {
+        return Err(());
+    }
+    Ok(filters)
+}
+
+/// Creates a filter from its amount.
+type AmountFilter = fn(f32) -> Filter;
+
+// <filter-function> = <grayscale()> | <sepia()> | <saturate()> | <hue-rotate()> | <invert()> |
+//                     <opacity()> | <brightness()> | <contrast()>
+fn parse_filter(input: &[u8]) -> Result<(&[u8], Filter), ()> {
+    let input = skip_ws(input);
+    let amounts: [(&[u8], AmountFilter); 7] = [
+        (b"grayscale", Filter::Grayscale),
+        (b"sepia", Filter::Sepia),
+        (b"saturate", Filter::Saturate),
+        (b"invert", Filter::Invert),
+        (b"opacity", Filter::Opacity),
+        (b"brightness", Filter::Brightness),
+        (b"contrast", Filter::Contrast),
+    ];
+    for (name, filter) in &amounts {
+        if let Ok(input) = consume_function(input, name) {
+            let (input, amount) = parse_amount(input)?;
+            return Ok((consume_close(input)?, filter(amount)));
+        }
+    }
+    if let Ok(input) = consume_function(input, b"hue-rotate") {
+        let (input, angle) = parse_angle(input)?;
+        return Ok((consume_close(input)?, Filter::HueRotate(angle)));
+    }
+    Err(())
+}
+
+// [ <number> | <percentage> ]?
+//
+// The amount defaults to 1 and must not be negative.
+fn parse_amount(input: &[u8]) -> Result<(&[u8], f32), ()> {
+    let (input, value) = match parse_number(input) {
+        Ok(result) => result,
+        Err(()) => return Ok((input, 1.)),
+    };
+    let (input, value) = match consume_byte(input, b'%') {
+        Ok(input) => (input, value / 100.),
+        Err(()) => (input, value),
+    };
+    if value < 0. {
+        return Err(());
+    }
+    Ok((input, value))
+}
+
+// [ <angle> | <zero> ]?
+fn parse_angle(input: &[u8]) -> Result<(&[u8], f32), ()> {
+    let (unitless, value) = match parse_number(input) {
+        Ok(result) => result,
+        Err(()) => return Ok((input, 0.)),
+    };
+    if !is_ident_start(unitless) {
+        return if value == 0. {
+            Ok((unitless, 0.))
+        } else {
+            Err(())
+        };
+    }
+    parse_hue(input)
+}
+
+fn consume_close(input: &[u8]) -> Result<&[u8], ()> {
+    consume_byte(skip_ws(input), b')')
+}
@@ -0,0 +1,347 @@
+use crate::{
+    color_f32_to_u8, parse_css_color, to_rgba8, ColorSpace, Hsl, Hwb, Lab, Lch, Oklab, Oklch,
+    PredefinedSpace, Srgb, NAMED_COLORS,
+};
+use std::fmt::{self, Write};
+
+/// The notation used by [`ColorFormat`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Notation {
+    /// `#rgb`. The alpha component is dropped and channels are rounded to 4 bits.
+    Hex3,
+    /// `#rgba`. Channels are rounded to 4 bits.
+    Hex4,
+    /// `#rrggbb`. The alpha component is dropped.
+    Hex6,
+    /// `#rrggbbaa`.
+    Hex8,
+    /// `rgb(r g b / a)`.
+    Rgb,
+    /// `rgb(r, g, b)` or `rgba(r, g, b, a)`.
+    LegacyRgb,
+    /// `hsl(h s l / a)`.
+    Hsl,
+    /// `hsl(h, s, l)` or `hsla(h, s, l, a)`.
+    LegacyHsl,
+    /// `hwb(h w b / a)`.
+    Hwb,
+    /// `lab(l a b / alpha)`.
+    Lab,
+    /// `lch(l c h / alpha)`.
+    Lch,
+    /// `oklab(l a b / alpha)`.
+    Oklab,
+    /// `oklch(l c h / alpha)`.
+    Oklch,
+    /// `color(space c1 c2 c3 / alpha)` in a predefined color space.
+    Color(PredefinedSpace),
+}
+
+impl Srgb {
+    /// Returns the shortest CSS string that parses to the same 8-bit color.
+    ///
+    /// The result is one of the hexadecimal notations or a named color. Hexadecimal notations are
+    /// preferred over named colors of the same length.
+    pub fn minify(&self) -> String {
+        let rgba8 = to_rgba8(*self);
+        let mut candidates = Vec::new();
+        for notation in [
+            Notation::Hex3,
+            Notation::Hex4,
+            Notation::Hex6,
+            Notation::Hex8,
+        ] {
+            candidates.push(ColorFormat::new(notation).format(*self));
+        }
+        for (name, _) in NAMED_COLORS.iter() {
+            candidates.push(name.to_string());
+        }
+        candidates.push("transparent".to_string());
+        candidates
+            .into_iter()
+            .filter(|candidate| parse_css_color(candidate.as_bytes()).map(to_rgba8) == Ok(rgba8))
+            .min_by_key(|candidate| candidate.len())
+            .unwrap()
+    }
+}
+
+/// Options for writing a color in a particular notation.
+///
+/// The canonical CSSOM serialization is available through [`Display`](fmt::Display) on
+/// [`Srgb`]. `ColorFormat` is for when a specific notation or style is required.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColorFormat {
+    /// The notation to write.
+    pub notation: Notation,
+    /// The maximum number of fractional digits for numeric components. Trailing zeros are
+    /// omitted. If `None`, components are written with as many digits as needed to represent
+    /// the value exactly.
+    pub precision: Option<usize>,
+    /// Whether to write percentages instead of numbers for the components that accept either.
+    ///
+    /// The legacy `hsl()` syntax always uses percentages. For the Lab and LCH notations, only the
+    /// lightness is affected. The `color()` notation always uses numbers, as CSSOM serializes it.
+    pub percentage: bool,
+    /// Whether to write the `none` keyword for powerless components in modern syntax.
+    pub none: bool,
+    /// Whether to write hexadecimal digits in upper case.
+    pub uppercase: bool,
+}
+
+impl ColorFormat {
+    /// Creates a format for the given notation with two fractional digits, percentages, no `none`
+    /// keywords and lower case hexadecimal digits.
+    pub fn new(notation: Notation) -> ColorFormat {
+        ColorFormat {
+            notation,
+            precision: Some(2),
+            percentage: true,
+            none: false,
+            uppercase: false,
+        }
+    }
+
+    /// Formats the color as a string.
+    pub fn format(&self, color: Srgb) -> String {
+        let mut s = String::new();
+        self.write(&mut s, color).unwrap();
+        s
+    }
+
+    /// Writes the color to the given writer.
+    pub fn write<W: Write>(&self, out: &mut W, color: Srgb) -> fmt::Result {
+        match self.notation {
+            Notation::Hex3 => self.write_hex(out, color, false, true),
+            Notation::Hex4 => self.write_hex(out, color, true, true),
+            Notation::Hex6 => self.write_hex(out, color, false, false),
+            Notation::Hex8 => self.write_hex(out, color, true, false),
+            Notation::Rgb | Notation::LegacyRgb => self.write_rgb(out, color),
+            Notation::Hsl | Notation::LegacyHsl => self.write_hsl(out, color),
+            Notation::Hwb => self.write_hwb(out, color),
+            Notation::Lab => {
+                let lab = color.convert::<Lab>();
+                self.write_lab(out, "lab", lab.lightness / 100., lab.a, lab.b, lab.alpha)
+            }
+            Notation::Lch => {
+                let lch = color.convert::<Lch>();
+                let powerless = lch.is_hue_powerless();
+                let (lightness, chroma) = (lch.lightness / 100., lch.chroma);
+                self.write_lch(out, "lch", lightness, chroma, lch.hue, powerless, lch.alpha)
+            }
+            Notation::Oklab => {
+                let oklab = color.convert::<Oklab>();
+                self.write_lab(out, "oklab", oklab.lightness, oklab.a, oklab.b, oklab.alpha)
+            }
+            Notation::Oklch => {
+                let oklch = color.convert::<Oklch>();
+                let powerless = oklch.is_hue_powerless();
+                let (lightness, chroma) = (oklch.lightness, oklch.chroma);
+                self.write_lch(
+                    out,
+                    "oklch",
+                    lightness,
+                    chroma,
+                    oklch.hue,
+                    powerless,
+                    oklch.alpha,
+                )
+            }
+            Notation::Color(space) => self.write_color(out, space, color),
+        }
+    }
+
+    fn legacy(&self) -> bool {
+        matches!(self.notation, Notation::LegacyRgb | Notation::LegacyHsl)
+    }
+
+    fn write_hex<W: Write>(
+        &self,
+        out: &mut W,
+        color: Srgb,
+        alpha: bool,
+        short: bool,
+    ) -> fmt::Result {
+        let channels = [color.red, color.green, color.blue, color.alpha];
+        let n = if alpha { 4 } else { 3 };
+        out.write_char('#')?;
+        for channel in &channels[..n] {
+            let value = color_f32_to_u8(*channel);
+            match (short, self.uppercase) {
+                (true, true) => write!(out, "{:X}", (value as f32 / 17.).round() as u8)?,
+                (true, false) => write!(out, "{:x}", (value as f32 / 17.).round() as u8)?,
+                (false, true) => write!(out, "{:02X}", value)?,
+                (false, false) => write!(out, "{:02x}", value)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_rgb<W: Write>(&self, out: &mut W, color: Srgb) -> fmt::Result {
+        let (name, separator) = self.function("rgb", color.alpha);
+        write!(out, "{}(", name)?;
+        for (i, channel) in [color.red, color.green, color.blue].iter().enumerate() {
+            if i > 0 {
+                out.write_str(separator)?;
+            }
+            if self.percentage {
+                self.write_percentage(out, *channel)?;
+            } else {
+                self.write_number(out, *channel * 255.)?;
+            }
+        }
+        self.write_alpha(out, color.alpha)
+    }
+
+    fn write_hsl<W: Write>(&self, out: &mut W, color: Srgb) -> fmt::Result {
+        let hsl = Hsl::from(color);
+        let (name, separator) = self.function("hsl", color.alpha);
+        write!(out, "{}(", name)?;
+        self.write_hue(out, hsl.hue, hsl.is_hue_powerless())?;
+        out.write_str(separator)?;
+        self.write_unit_interval(out, hsl.saturation)?;
+        out.write_str(separator)?;
+        self.write_unit_interval(out, hsl.lightness)?;
+        self.write_alpha(out, color.alpha)
+    }
+
+    fn write_hwb<W: Write>(&self, out: &mut W, color: Srgb) -> fmt::Result {
+        let hwb = Hwb::from(color);
+        out.write_str("hwb(")?;
+        self.write_hue(out, hwb.hue, hwb.is_hue_powerless())?;
+        out.write_char(' ')?;
+        self.write_unit_interval(out, hwb.whiteness)?;
+        out.write_char(' ')?;
+        self.write_unit_interval(out, hwb.blackness)?;
+        self.write_alpha(out, color.alpha)
+    }
+
+    fn write_color<W: Write>(
+        &self,
+        out: &mut W,
+        space: PredefinedSpace,
+        color: Srgb,
+    ) -> fmt::Result {
+        write!(out, "color({}", space.name())?;
+        for component in space.components(color).iter() {
+            out.write_char(' ')?;
+            self.write_number(out, *component)?;
+        }
+        self.write_alpha(out, color.alpha)
+    }
+
+    /// Writes a Lab-like color. The lightness is given as a fraction of the reference range.
+    fn write_lab<W: Write>(
+        &self,
+        out: &mut W,
+        name: &str,
+        lightness: f32,
+        a: f32,
+        b: f32,
+        alpha: f32,
+    ) -> fmt::Result {
+        write!(out, "{}(", name)?;
+        self.write_lightness(out, name, lightness)?;
+        out.write_char(' ')?;
+        self.write_number(out, a)?;
+        out.write_char(' ')?;
+        self.write_number(out, b)?;
+        self.write_alpha(out, alpha)
+    }
+
+    /// Writes an LCH-like color. The lightness is given as a fraction of the reference range.
+    #[allow(clippy::too_many_arguments)]
+    fn write_lch<W: Write>(
+        &self,
+        out: &mut W,
+        name: &str,
+        lightness: f32,
+        chroma: f32,
+        hue: f32,
+        powerless: bool,
+        alpha: f32,
+    ) -> fmt::Result {
+        write!(out, "{}(", name)?;
+        self.write_lightness(out, name, lightness)?;
+        out.write_char(' ')?;
+        self.write_number(out, chroma)?;
+        out.write_char(' ')?;
+        self.write_hue(out, hue, powerless)?;
+        self.write_alpha(out, alpha)
+    }
+
+    fn write_lightness<W: Write>(&self, out: &mut W, name: &str, lightness: f32) -> fmt::Result {
+        if self.percentage {
+            self.write_percentage(out, lightness)
+        } else if name.starts_with("ok") {
+            self.write_number(out, lightness)
+        } else {
+            self.write_number(out, lightness * 100.)
+        }
+    }
+
+    /// Returns the function name and component separator for the notation.
+    fn function(&self, name: &'static str, alpha: f32) -> (&'static str, &'static str) {
+        if !self.legacy() {
+            (name, " ")
+        } else if alpha == 1. {
+            (name, ", ")
+        } else {
+            (if name == "rgb" { "rgba" } else { "hsla" }, ", ")
+        }
+    }
+
+    fn write_hue<W: Write>(&self, out: &mut W, hue: f32, powerless: bool) -> fmt::Result {
+        if powerless && self.none && !self.legacy() {
+            out.write_str("none")
+        } else {
+            self.write_number(out, hue)
+        }
+    }
+
+    fn write_unit_interval<W: Write>(&self, out: &mut W, value: f32) -> fmt::Result {
+        if self.percentage || self.legacy() {
+            self.write_percentage(out, value)
+        } else {
+            self.write_number(out, value * 100.)
+        }
+    }
+
+    fn write_alpha<W: Write>(&self, out: &mut W, alpha: f32) -> fmt::Result {
+        if alpha == 1. {
+            return out.write_char(')');
+        }
+        out.write_str(if self.legacy() { ", " } else { " / " })?;
+        self.write_number(out, alpha)?;
+        out.write_char(')')
+    }
+
+    fn write_percentage<W: Write>(&self, out: &mut W, value: f32) -> fmt::Result {
+        self.write_number(out, value * 100.)?;
+        out.write_char('%')
+    }
+
+    fn write_number<W: Write>(&self, out: &mut W, value: f32) -> fmt::Result {
+        write_number(out, value, self.precision)
+    }
+}
+
+/// Writes a number with at most `precision` fractional digits and no trailing zeros.
+fn write_number<W: Write>(out: &mut W, value: f32, precision: Option<usize>) -> fmt::Result {
+    let s = match precision {
+        Some(precision) => {
+            let s = format!("{:.*}", precision, value);
+            if s.contains('.') {
+                s.trim_end_matches('0').trim_end_matches('.').to_string()
+            } else {
+                s
+            }
+        }
+        None => value.to_string(),
+    };
+    // Negative values that round to zero are written as zero.
+    if s == "-0" {
+        out.write_char('0')
+    } else {
+        out.write_str(&s)
+    }
+}
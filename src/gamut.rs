@@ -0,0 +1,107 @@
+use crate::difference::delta_eok;
+use crate::space::ColorSpace;
+use crate::{clamp_unit_f32, DisplayP3, LinearSrgb, Oklch, Rec2020, Srgb};
+
+/// An RGB color space whose gamut is the unit cube of its components.
+pub trait RgbColorSpace: ColorSpace {
+    /// Returns the red, green and blue components.
+    fn rgb(&self) -> [f32; 3];
+
+    /// Returns the alpha component.
+    fn alpha(&self) -> f32;
+
+    /// Creates a color from red, green, blue and alpha components.
+    fn from_rgb(rgb: [f32; 3], alpha: f32) -> Self;
+
+    /// Returns `true` if every component is within the gamut, allowing for rounding error.
+    fn is_in_gamut(&self) -> bool {
+        self.rgb()
+            .iter()
+            .all(|value| (-GAMUT_EPSILON..=1. + GAMUT_EPSILON).contains(value))
+    }
+
+    /// Clamps every component to the gamut. NaN components become 0.
+    fn clip(&self) -> Self {
+        Self::from_rgb(self.rgb().map(clamp_unit_f32), self.alpha())
+    }
+}
+
+/// Tolerance for floating point error when checking whether a color is in gamut.
+const GAMUT_EPSILON: f32 = 0.000075;
+
+macro_rules! impl_rgb_color_space {
+    ($($ty:ident),*) => {
+        $(
+            impl RgbColorSpace for $ty {
+                fn rgb(&self) -> [f32; 3] {
+                    [self.red, self.green, self.blue]
+                }
+
+                fn alpha(&self) -> f32 {
+                    self.alpha
+                }
+
+                fn from_rgb([red, green, blue]: [f32; 3], alpha: f32) -> Self {
+                    $ty::new(red, green, blue, alpha)
+                }
+            }
+        )*
+    };
+}
+
+impl_rgb_color_space!(Srgb, LinearSrgb, DisplayP3, Rec2020);
+
+/// The just noticeable difference in Oklab used by gamut mapping.
+const JND: f32 = 0.02;
+
+/// The chroma precision of the binary search used by gamut mapping.
+const CHROMA_EPSILON: f32 = 0.0001;
+
+/// Maps a color into the gamut of an RGB color space.
+///
+/// This is the CSS gamut mapping algorithm: the chroma is reduced in Oklch, keeping lightness and
+/// hue, until clipping the color changes it by less than a just noticeable difference.
+///
+/// <https://www.w3.org/TR/css-color-4/#binsearch>
+pub(crate) fn gamut_map<T: RgbColorSpace>(origin: Oklch) -> T {
+    if origin.lightness >= 1. {
+        return T::from_rgb([1., 1., 1.], origin.alpha);
+    }
+    if origin.lightness <= 0. {
+        return T::from_rgb([0., 0., 0.], origin.alpha);
+    }
+    let destination = origin.convert::<T>();
+    if destination.is_in_gamut() {
+        return destination;
+    }
+
+    let mut current = origin;
+    let mut clipped = destination.clip();
+    if delta_eok(clipped.convert(), current.convert()) < JND {
+        return clipped;
+    }
+    let mut min = 0.;
+    let mut max = origin.chroma;
+    let mut min_in_gamut = true;
+    while max - min > CHROMA_EPSILON {
+        let chroma = (min + max) / 2.;
+        current.chroma = chroma;
+        let destination = current.convert::<T>();
+        if min_in_gamut && destination.is_in_gamut() {
+            min = chroma;
+            continue;
+        }
+        clipped = destination.clip();
+        let e = delta_eok(clipped.convert(), current.convert());
+        if e < JND {
+            if JND - e < CHROMA_EPSILON {
+                break;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+    clipped
+}
@@ -0,0 +1,801 @@
+use crate::interpolate::lerp;
+use crate::{
+    consume_function, consume_name, is_ident_start, is_whitespace, parse_color, parse_hue,
+    parse_number, parse_percentage, skip_ws, ColorSpace, HueInterpolation, InterpolationSpace,
+    ParseColorError, RgbColorSpace, Srgb,
+};
+use std::str::FromStr;
+
+/// A CSS gradient function.
+///
+/// <https://www.w3.org/TR/css-images-4/#gradients>
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    /// The shape of the gradient.
+    pub kind: GradientKind,
+    /// Whether the color stops repeat, as in `repeating-linear-gradient()`.
+    pub repeating: bool,
+    /// The color stops. There are always at least two.
+    pub stops: Vec<ColorStop>,
+    /// The color space in which to interpolate between stops.
+    pub space: InterpolationSpace,
+    /// How to interpolate hues in cylindrical color spaces.
+    pub hue: HueInterpolation,
+}
+
+/// The shape of a [`Gradient`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientKind {
+    /// `linear-gradient()`.
+    Linear {
+        /// The direction of the gradient line.
+        direction: LinearDirection,
+    },
+    /// `radial-gradient()`.
+    Radial {
+        /// The ending shape.
+        shape: RadialShape,
+        /// The size of the ending shape.
+        size: RadialSize,
+        /// The center of the gradient.
+        center: Position,
+    },
+    /// `conic-gradient()`.
+    Conic {
+        /// The angle in degrees of the start of the gradient, clockwise from the top.
+        from: f32,
+        /// The center of the gradient.
+        center: Position,
+    },
+}
+
+/// The direction of a linear gradient.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinearDirection {
+    /// An angle in degrees, clockwise from the top. `to top` and the other sides are converted to
+    /// angles.
+    Angle(f32),
+    /// `to` a corner. The angle depends on the aspect ratio of the box.
+    Corner {
+        /// `right` if `true`, `left` otherwise.
+        right: bool,
+        /// `bottom` if `true`, `top` otherwise.
+        bottom: bool,
+    },
+}
+
+/// The ending shape of a radial gradient.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RadialShape {
+    Circle,
+    Ellipse,
+}
+
+/// The size of the ending shape of a radial gradient.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RadialSize {
+    ClosestSide,
+    ClosestCorner,
+    FarthestSide,
+    FarthestCorner,
+    /// The horizontal and vertical radii. Both are equal for circles.
+    Explicit(LengthPercentage, LengthPercentage),
+}
+
+/// A position within the box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    /// The offset from the left edge.
+    pub x: LengthPercentage,
+    /// The offset from the top edge.
+    pub y: LengthPercentage,
+}
+
+/// A length or a percentage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LengthPercentage {
+    /// A length in pixels. Positions of conic gradient stops are angles in degrees instead.
+    Length(f32),
+    /// A percentage as a fraction, where 1 is 100%.
+    Percentage(f32),
+}
+
+impl LengthPercentage {
+    fn resolve(self, length: f32) -> f32 {
+        match self {
+            LengthPercentage::Length(value) => value,
+            LengthPercentage::Percentage(value) => value * length,
+        }
+    }
+}
+
+/// A color stop of a [`Gradient`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorStop {
+    /// The color.
+    pub color: Srgb,
+    /// The position along the gradient line. If `None`, the stop is placed halfway between its
+    /// neighbors.
+    pub position: Option<LengthPercentage>,
+    /// The position of the transition hint between this stop and the next, if any.
+    pub hint: Option<LengthPercentage>,
+}
+
+impl FromStr for Gradient {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_gradient(s.as_bytes()).map_err(|_| ParseColorError)
+    }
+}
+
+impl Gradient {
+    /// Returns the color at `t` along the gradient line, where 0 is the start and 1 is the end.
+    ///
+    /// `length` is the length of the gradient line in pixels, used to resolve stops positioned
+    /// with lengths. For conic gradients, `t` is a fraction of a turn and `length` is 360. The
+    /// result is gamut mapped to sRGB. A gradient without stops is transparent black.
+    pub fn sample(&self, t: f32, length: f32) -> Srgb {
+        if self.stops.is_empty() {
+            return Srgb::new(0., 0., 0., 0.);
+        }
+        let resolve = |position: LengthPercentage| match position {
+            LengthPercentage::Length(value) if length > 0. => value / length,
+            LengthPercentage::Length(_) => 0.,
+            LengthPercentage::Percentage(value) => value,
+        };
+        let (positions, hints) = self.fixup(resolve);
+        let n = positions.len();
+        let (first, last) = (positions[0], positions[n - 1]);
+
+        let mut t = t;
+        if self.repeating {
+            if last - first <= 0. {
+                // https://www.w3.org/TR/css-images-4/#repeating-gradients
+                return to_display(self.average_color());
+            }
+            t = first + (t - first).rem_euclid(last - first);
+        }
+        if t < first {
+            return to_display(self.stops[0].color);
+        }
+        let i = match (0..n - 1).find(|i| t < positions[i + 1]) {
+            Some(i) => i,
+            None => return to_display(self.stops[n - 1].color),
+        };
+
+        let (start, end) = (positions[i], positions[i + 1]);
+        let mut local = (t - start) / (end - start);
+        if let Some(hint) = hints[i] {
+            // https://www.w3.org/TR/css-images-4/#coloring-gradient-line
+            let hint = ((hint - start) / (end - start)).clamp(0., 1.);
+            local = if hint <= 0. {
+                1.
+            } else if hint >= 1. {
+                0.
+            } else {
+                local.powf(0.5_f32.ln() / hint.ln())
+            };
+        }
+        let from = self.stops[i].color;
+        let to = self.stops[i + 1].color;
+        to_display(from.interpolate(to, local, self.space, self.hue))
+    }
+
+    /// Returns the average color of the gradient with its stops evenly spaced, ignoring hints,
+    /// which is how a repeating gradient of zero length is rendered.
+    fn average_color(&self) -> Srgb {
+        let mut average = self.stops[0].color;
+        for (k, pair) in self.stops.windows(2).enumerate() {
+            // Interpolation is linear in the interpolation space, so the average of a segment is
+            // its midpoint. Midpoints are then averaged incrementally.
+            let midpoint = pair[0]
+                .color
+                .interpolate(pair[1].color, 0.5, self.space, self.hue);
+            average = if k == 0 {
+                midpoint
+            } else {
+                average.interpolate(midpoint, 1. / (k + 1) as f32, self.space, self.hue)
+            };
+        }
+        average
+    }
+
+    /// Returns the color at a point of a box with the given size, with the origin at the top left.
+    pub fn color_at(&self, x: f32, y: f32, width: f32, height: f32) -> Srgb {
+        match self.kind {
+            GradientKind::Linear { direction } => {
+                let angle = match direction {
+                    LinearDirection::Angle(angle) => angle.to_radians(),
+                    LinearDirection::Corner { right, bottom } => {
+                        // The gradient line is perpendicular to the diagonal through the other
+                        // two corners.
+                        let dx = if right { height } else { -height };
+                        let dy = if bottom { width } else { -width };
+                        dx.atan2(-dy)
+                    }
+                };
+                let (sin, cos) = angle.sin_cos();
+                let length = (width * sin).abs() + (height * cos).abs();
+                let offset = (x - width / 2.) * sin - (y - height / 2.) * cos;
+                let t = if length > 0. {
+                    offset / length + 0.5
+                } else {
+                    0.
+                };
+                self.sample(t, length)
+            }
+            GradientKind::Radial {
+                shape,
+                size,
+                center,
+            } => {
+                let cx = center.x.resolve(width);
+                let cy = center.y.resolve(height);
+                let (rx, ry) = radii(shape, size, cx, cy, width, height);
+                let (dx, dy) = (x - cx, y - cy);
+                if rx <= 0. || ry <= 0. {
+                    return self.sample(f32::INFINITY, 0.);
+                }
+                let t = ((dx / rx).powi(2) + (dy / ry).powi(2)).sqrt();
+                self.sample(t, rx)
+            }
+            GradientKind::Conic { from, center } => {
+                let cx = center.x.resolve(width);
+                let cy = center.y.resolve(height);
+                let angle = (x - cx).atan2(cy - y).to_degrees() - from;
+                self.sample(angle.rem_euclid(360.) / 360., 360.)
+            }
+        }
+    }
+
+    /// Resolves the positions of color stops and transition hints.
+    ///
+    /// <https://www.w3.org/TR/css-images-4/#color-stop-fixup>
+    fn fixup(&self, resolve: impl Fn(LengthPercentage) -> f32) -> (Vec<f32>, Vec<Option<f32>>) {
+        let n = self.stops.len();
+        let mut positions: Vec<_> = self
+            .stops
+            .iter()
+            .map(|stop| stop.position.map(&resolve))
+            .collect();
+        let mut hints: Vec<_> = self
+            .stops
+            .iter()
+            .map(|stop| stop.hint.map(&resolve))
+            .collect();
+        positions[0].get_or_insert(0.);
+        positions[n - 1].get_or_insert(1.);
+
+        // Positions may not decrease.
+        let mut max = f32::NEG_INFINITY;
+        for (position, hint) in positions.iter_mut().zip(&mut hints) {
+            for value in position.iter_mut().chain(hint.iter_mut()) {
+                *value = value.max(max);
+                max = *value;
+            }
+        }
+
+        // Runs of stops without positions are spaced evenly.
+        let mut resolved = vec![0.; n];
+        let mut previous = 0;
+        for i in 0..n {
+            if let Some(position) = positions[i] {
+                for j in previous + 1..i {
+                    let fraction = (j - previous) as f32 / (i - previous) as f32;
+                    resolved[j] = lerp(resolved[previous], position, fraction);
+                }
+                resolved[i] = position;
+                previous = i;
+            }
+        }
+        (resolved, hints)
+    }
+}
+
+/// Gamut maps a color to sRGB, keeping colors that are already in gamut exact.
+fn to_display(color: Srgb) -> Srgb {
+    if color.is_in_gamut() {
+        color.clip()
+    } else {
+        color.to_gamut()
+    }
+}
+
+/// Returns the horizontal and vertical radii of a radial gradient.
+///
+/// <https://www.w3.org/TR/css-images-4/#radial-gradient-syntax>
+fn radii(shape: RadialShape, size: RadialSize, cx: f32, cy: f32, w: f32, h: f32) -> (f32, f32) {
+    let (near_x, far_x) = (cx.abs().min((w - cx).abs()), cx.abs().max((w - cx).abs()));
+    let (near_y, far_y) = (cy.abs().min((h - cy).abs()), cy.abs().max((h - cy).abs()));
+    match (shape, size) {
+        (_, RadialSize::Explicit(rx, ry)) => (rx.resolve(w), ry.resolve(h)),
+        (RadialShape::Circle, RadialSize::ClosestSide) => {
+            let r = near_x.min(near_y);
+            (r, r)
+        }
+        (RadialShape::Circle, RadialSize::FarthestSide) => {
+            let r = far_x.max(far_y);
+            (r, r)
+        }
+        (RadialShape::Circle, RadialSize::ClosestCorner) => {
+            let r = near_x.hypot(near_y);
+            (r, r)
+        }
+        (RadialShape::Circle, RadialSize::FarthestCorner) => {
+            let r = far_x.hypot(far_y);
+            (r, r)
+        }
+        (RadialShape::Ellipse, RadialSize::ClosestSide) => (near_x, near_y),
+        (RadialShape::Ellipse, RadialSize::FarthestSide) => (far_x, far_y),
+        // Corner sizes keep the aspect ratio of the matching side size.
+        (RadialShape::Ellipse, RadialSize::ClosestCorner) => (
+            near_x * std::f32::consts::SQRT_2,
+            near_y * std::f32::consts::SQRT_2,
+        ),
+        (RadialShape::Ellipse, RadialSize::FarthestCorner) => (
+            far_x * std::f32::consts::SQRT_2,
+            far_y * std::f32::consts::SQRT_2,
+        ),
+    }
+}
+
+fn parse_gradient(input: &[u8]) -> Result<Gradient, ()> {
+    type Parser = fn(&[&[u8]]) -> Result<GradientKind, ()>;
+    let functions: [(&[u8], bool, Parser); 6] = [
+        (b"linear-gradient", false, parse_linear),
+        (b"radial-gradient", false, parse_radial),
+        (b"conic-gradient", false, parse_conic),
+        (b"repeating-linear-gradient", true, parse_linear),
+        (b"repeating-radial-gradient", true, parse_radial),
+        (b"repeating-conic-gradient", true, parse_conic),
+    ];
+    let (input, name, repeating, parse_kind) = functions
+        .iter()
+        .find_map(|(name, repeating, parse)| {
+            consume_function(input, name)
+                .ok()
+                .map(|input| (input, name, *repeating, parse))
+        })
+        .ok_or(())?;
+    let input = match input.split_last() {
+        Some((b')', input)) => input,
+        _ => return Err(()),
+    };
+
+    let arguments = split_top_level(input, |c| c == b',')?;
+    let conic = name.ends_with(b"conic-gradient");
+
+    // The first argument configures the gradient if it isn't a color stop.
+    let mut arguments = &arguments[..];
+    let mut kind = parse_kind(&[]).unwrap();
+    let mut method = None;
+    if let Some(first) = arguments.first() {
+        let tokens = split_top_level(first, is_whitespace)?;
+        if let Ok((geometry, interpolation)) = split_interpolation(&tokens) {
+            if let Ok(parsed) = parse_kind(geometry) {
+                kind = parsed;
+                method = interpolation;
+                arguments = &arguments[1..];
+            }
+        }
+    }
+
+    let (stops, legacy) = parse_stops(arguments, conic)?;
+    // Like browsers, gradients of legacy sRGB colors interpolate in sRGB by default.
+    let (space, hue) = method.unwrap_or(if legacy {
+        (InterpolationSpace::Srgb, HueInterpolation::Shorter)
+    } else {
+        (InterpolationSpace::Oklab, HueInterpolation::Shorter)
+    });
+    Ok(Gradient {
+        kind,
+        repeating,
+        stops,
+        space,
+        hue,
+    })
+}
+
+type Method = (InterpolationSpace, HueInterpolation);
+
+/// Splits the tokens of the first argument into the geometry and the color interpolation method,
+/// which may come before or after it.
+fn split_interpolation<'a, 'b>(
+    tokens: &'b [&'a [u8]],
+) -> Result<(&'b [&'a [u8]], Option<Method>), ()> {
+    let start = match tokens
+        .iter()
+        .position(|token| consume_name(token, b"in") == Ok(b""))
+    {
+        Some(start) => start,
+        None => return Ok((tokens, None)),
+    };
+    let (method, consumed) = parse_interpolation_method(&tokens[start + 1..])?;
+    let end = start + 1 + consumed;
+    if start == 0 {
+        Ok((&tokens[end..], Some(method)))
+    } else if end == tokens.len() {
+        Ok((&tokens[..start], Some(method)))
+    } else {
+        Err(())
+    }
+}
+
+/// Parses a `<color-interpolation-method>` after the `in` keyword. Returns the method and the
+/// number of tokens consumed.
+///
+/// <https://www.w3.org/TR/css-color-4/#color-interpolation-method>
+pub(crate) fn parse_interpolation_method(tokens: &[&[u8]]) -> Result<(Method, usize), ()> {
+    let spaces: [(&[u8], InterpolationSpace); 13] = [
+        (b"srgb", InterpolationSpace::Srgb),
+        (b"srgb-linear", InterpolationSpace::SrgbLinear),
+        (b"display-p3", InterpolationSpace::DisplayP3),
+        (b"rec2020", InterpolationSpace::Rec2020),
+        (b"lab", InterpolationSpace::Lab),
+        (b"oklab", InterpolationSpace::Oklab),
+        (b"xyz", InterpolationSpace::XyzD65),
+        (b"xyz-d50", InterpolationSpace::XyzD50),
+        (b"xyz-d65", InterpolationSpace::XyzD65),
+        (b"hsl", InterpolationSpace::Hsl),
+        (b"hwb", InterpolationSpace::Hwb),
+        (b"lch", InterpolationSpace::Lch),
+        (b"oklch", InterpolationSpace::Oklch),
+    ];
+    let methods: [(&[u8], HueInterpolation); 4] = [
+        (b"shorter", HueInterpolation::Shorter),
+        (b"longer", HueInterpolation::Longer),
+        (b"increasing", HueInterpolation::Increasing),
+        (b"decreasing", HueInterpolation::Decreasing),
+    ];
+    let keyword = |token: &[u8], name: &[u8]| consume_name(token, name) == Ok(b"");
+
+    let space = tokens
+        .first()
+        .and_then(|token| spaces.iter().find(|(name, _)| keyword(token, name)))
+        .map(|(_, space)| *space)
+        .ok_or(())?;
+    if space.hue_index().is_some() && tokens.len() >= 3 && keyword(tokens[2], b"hue") {
+        let hue = methods
+            .iter()
+            .find(|(name, _)| keyword(tokens[1], name))
+            .map(|(_, hue)| *hue)
+            .ok_or(())?;
+        return Ok(((space, hue), 3));
+    }
+    Ok(((space, HueInterpolation::Shorter), 1))
+}
+
+// [ <angle> | to <side-or-corner> ]?
+fn parse_linear(tokens: &[&[u8]]) -> Result<GradientKind, ()> {
+    let direction = match tokens {
+        [] => LinearDirection::Angle(180.),
+        [angle] => LinearDirection::Angle(parse_angle(angle)?),
+        [to, rest @ ..] if consume_name(to, b"to") == Ok(b"") => {
+            let mut horizontal = None;
+            let mut vertical = None;
+            for token in rest {
+                let side = parse_keyword(token)?;
+                let slot = match side {
+                    Keyword::Left | Keyword::Right => &mut horizontal,
+                    Keyword::Top | Keyword::Bottom => &mut vertical,
+                    Keyword::Center => return Err(()),
+                };
+                if slot.replace(side).is_some() {
+                    return Err(());
+                }
+            }
+            match (horizontal, vertical) {
+                (Some(h), Some(v)) => LinearDirection::Corner {
+                    right: h == Keyword::Right,
+                    bottom: v == Keyword::Bottom,
+                },
+                (Some(Keyword::Left), None) => LinearDirection::Angle(270.),
+                (Some(_), None) => LinearDirection::Angle(90.),
+                (None, Some(Keyword::Top)) => LinearDirection::Angle(0.),
+                (None, Some(_)) => LinearDirection::Angle(180.),
+                (None, None) => return Err(()),
+            }
+        }
+        _ => return Err(()),
+    };
+    Ok(GradientKind::Linear { direction })
+}
+
+// [ <radial-shape> || <radial-size> ]? [ at <position> ]?
+fn parse_radial(tokens: &[&[u8]]) -> Result<GradientKind, ()> {
+    let (tokens, center) = parse_at_position(tokens)?;
+    let mut shape = None;
+    let mut size = None;
+    let mut lengths = Vec::new();
+    for token in tokens {
+        let keyword = |name: &[u8]| consume_name(token, name) == Ok(b"");
+        if keyword(b"circle") && shape.is_none() {
+            shape = Some(RadialShape::Circle);
+        } else if keyword(b"ellipse") && shape.is_none() {
+            shape = Some(RadialShape::Ellipse);
+        } else if size.is_none() && lengths.is_empty() {
+            size = Some(if keyword(b"closest-side") {
+                RadialSize::ClosestSide
+            } else if keyword(b"closest-corner") {
+                RadialSize::ClosestCorner
+            } else if keyword(b"farthest-side") {
+                RadialSize::FarthestSide
+            } else if keyword(b"farthest-corner") {
+                RadialSize::FarthestCorner
+            } else {
+                lengths.push(parse_length_percentage(token)?);
+                continue;
+            });
+        } else if size.is_none() && lengths.len() == 1 {
+            lengths.push(parse_length_percentage(token)?);
+        } else {
+            return Err(());
+        }
+    }
+
+    let (shape, size) = match (shape, &lengths[..]) {
+        // A circle's radius can't be a percentage.
+        (Some(RadialShape::Circle) | None, &[LengthPercentage::Length(r)]) => (
+            RadialShape::Circle,
+            RadialSize::Explicit(LengthPercentage::Length(r), LengthPercentage::Length(r)),
+        ),
+        (Some(RadialShape::Ellipse) | None, &[rx, ry]) => {
+            (RadialShape::Ellipse, RadialSize::Explicit(rx, ry))
+        }
+        (shape, []) => (
+            shape.unwrap_or(RadialShape::Ellipse),
+            size.unwrap_or(RadialSize::FarthestCorner),
+        ),
+        _ => return Err(()),
+    };
+    Ok(GradientKind::Radial {
+        shape,
+        size,
+        center,
+    })
+}
+
+// [ from <angle> ]? [ at <position> ]?
+fn parse_conic(tokens: &[&[u8]]) -> Result<GradientKind, ()> {
+    let (tokens, center) = parse_at_position(tokens)?;
+    let from = match tokens {
+        [] => 0.,
+        [from, angle] if consume_name(from, b"from") == Ok(b"") => parse_angle(angle)?,
+        _ => return Err(()),
+    };
+    Ok(GradientKind::Conic { from, center })
+}
+
+/// Splits off a trailing `at <position>`, which defaults to the center.
+fn parse_at_position<'a, 'b>(tokens: &'b [&'a [u8]]) -> Result<(&'b [&'a [u8]], Position), ()> {
+    let center = Position {
+        x: LengthPercentage::Percentage(0.5),
+        y: LengthPercentage::Percentage(0.5),
+    };
+    match tokens
+        .iter()
+        .position(|token| consume_name(token, b"at") == Ok(b""))
+    {
+        Some(at) => Ok((&tokens[..at], parse_position(&tokens[at + 1..])?)),
+        None => Ok((tokens, center)),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Keyword {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Center,
+}
+
+fn parse_keyword(token: &[u8]) -> Result<Keyword, ()> {
+    let keywords: [(&[u8], Keyword); 5] = [
+        (b"left", Keyword::Left),
+        (b"right", Keyword::Right),
+        (b"top", Keyword::Top),
+        (b"bottom", Keyword::Bottom),
+        (b"center", Keyword::Center),
+    ];
+    keywords
+        .iter()
+        .find(|(name, _)| consume_name(token, name) == Ok(b""))
+        .map(|(_, keyword)| *keyword)
+        .ok_or(())
+}
+
+// <position> with one or two values.
+fn parse_position(tokens: &[&[u8]]) -> Result<Position, ()> {
+    let value = |keyword| match keyword {
+        Keyword::Left | Keyword::Top => LengthPercentage::Percentage(0.),
+        Keyword::Right | Keyword::Bottom => LengthPercentage::Percentage(1.),
+        Keyword::Center => LengthPercentage::Percentage(0.5),
+    };
+    let horizontal = |k| matches!(k, Keyword::Left | Keyword::Right | Keyword::Center);
+    let vertical = |k| matches!(k, Keyword::Top | Keyword::Bottom | Keyword::Center);
+    let center = LengthPercentage::Percentage(0.5);
+
+    match *tokens {
+        [token] => match parse_keyword(token) {
+            Ok(k @ (Keyword::Top | Keyword::Bottom)) => Ok(Position {
+                x: center,
+                y: value(k),
+            }),
+            Ok(k) => Ok(Position {
+                x: value(k),
+                y: center,
+            }),
+            Err(()) => Ok(Position {
+                x: parse_length_percentage(token)?,
+                y: center,
+            }),
+        },
+        [first, second] => match (parse_keyword(first), parse_keyword(second)) {
+            (Ok(a), Ok(b)) if horizontal(a) && vertical(b) => Ok(Position {
+                x: value(a),
+                y: value(b),
+            }),
+            (Ok(a), Ok(b)) if vertical(a) && horizontal(b) => Ok(Position {
+                x: value(b),
+                y: value(a),
+            }),
+            (Ok(a), Err(())) if horizontal(a) => Ok(Position {
+                x: value(a),
+                y: parse_length_percentage(second)?,
+            }),
+            (Err(()), Ok(b)) if vertical(b) => Ok(Position {
+                x: parse_length_percentage(first)?,
+                y: value(b),
+            }),
+            (Err(()), Err(())) => Ok(Position {
+                x: parse_length_percentage(first)?,
+                y: parse_length_percentage(second)?,
+            }),
+            _ => Err(()),
+        },
+        _ => Err(()),
+    }
+}
+
+/// Parses the color stops and transition hints. Also returns whether every color uses a legacy
+/// sRGB syntax.
+fn parse_stops(arguments: &[&[u8]], conic: bool) -> Result<(Vec<ColorStop>, bool), ()> {
+    let position = |token: &[u8]| {
+        if conic {
+            parse_angle_percentage(token)
+        } else {
+            parse_length_percentage(token)
+        }
+    };
+
+    let mut stops: Vec<ColorStop> = Vec::new();
+    let mut legacy = true;
+    for argument in arguments {
+        let tokens = split_top_level(argument, is_whitespace)?;
+        match tokens[..] {
+            // A transition hint must follow a color stop.
+            [hint] if parse_color(hint, false).is_err() => {
+                let previous = stops.last_mut().filter(|stop| stop.hint.is_none());
+                previous.ok_or(())?.hint = Some(position(hint)?);
+            }
+            [color, ref positions @ ..] if positions.len() <= 2 => {
+                // Stops keep colors outside sRGB for interpolation in wider color spaces.
+                let color_value = parse_color(color, false)?;
+                legacy &= consume_function(color, b"color").is_err();
+                let mut positions = positions.iter().map(|token| position(token));
+                stops.push(ColorStop {
+                    color: color_value,
+                    position: positions.next().transpose()?,
+                    hint: None,
+                });
+                // A double-position stop is two stops with the same color.
+                if let Some(second) = positions.next() {
+                    stops.push(ColorStop {
+                        color: color_value,
+                        position: Some(second?),
+                        hint: None,
+                    });
+                }
+            }
+            _ => return Err(()),
+        }
+    }
+    if stops.len() < 2 || stops[stops.len() - 1].hint.is_some() {
+        return Err(());
+    }
+    Ok((stops, legacy))
+}
+
+/// Splits the input at separators outside of parentheses, trimming whitespace. Empty parts are
+/// skipped when the separator is whitespace and rejected otherwise.
+pub(crate) fn split_top_level(
+    input: &[u8],
+    separator: impl Fn(u8) -> bool,
+) -> Result<Vec<&[u8]>, ()> {
+    let whitespace = separator(b' ');
+    let mut parts = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    for (i, c) in input.iter().enumerate() {
+        match c {
+            b'(' => depth += 1,
+            b')' => depth = depth.checked_sub(1).ok_or(())?,
+            c if depth == 0 && separator(*c) => {
+                parts.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return Err(());
+    }
+    parts.push(&input[start..]);
+
+    let mut result = Vec::new();
+    for part in parts {
+        let part = trim(part);
+        if !part.is_empty() {
+            result.push(part);
+        } else if !whitespace {
+            return Err(());
+        }
+    }
+    Ok(result)
+}
+
+pub(crate) fn trim(input: &[u8]) -> &[u8] {
+    let input = skip_ws(input);
+    let end = input
+        .iter()
+        .rposition(|c| !is_whitespace(*c))
+        .map_or(0, |i| i + 1);
+    &input[..end]
+}
+
+// <angle>, where unitless zero is allowed.
+fn parse_angle(token: &[u8]) -> Result<f32, ()> {
+    let (rest, value) = parse_number(token)?;
+    if !is_ident_start(rest) && value != 0. {
+        return Err(());
+    }
+    match parse_hue(token)? {
+        (b"", degrees) => Ok(degrees),
+        _ => Err(()),
+    }
+}
+
+// <angle-percentage>. Angles are returned as lengths in degrees.
+fn parse_angle_percentage(token: &[u8]) -> Result<LengthPercentage, ()> {
+    match parse_percentage(token) {
+        Ok((b"", value)) => Ok(LengthPercentage::Percentage(value)),
+        _ => parse_angle(token).map(LengthPercentage::Length),
+    }
+}
+
+// <length-percentage> with absolute lengths.
+fn parse_length_percentage(token: &[u8]) -> Result<LengthPercentage, ()> {
+    if let Ok((b"", value)) = parse_percentage(token) {
+        return Ok(LengthPercentage::Percentage(value));
+    }
+    let (unit, value) = parse_number(token)?;
+    let units: [(&[u8], f32); 7] = [
+        (b"px", 1.),
+        (b"cm", 96. / 2.54),
+        (b"mm", 96. / 25.4),
+        (b"q", 96. / 101.6),
+        (b"in", 96.),
+        (b"pt", 96. / 72.),
+        (b"pc", 16.),
+    ];
+    if unit.is_empty() && value == 0. {
+        return Ok(LengthPercentage::Length(0.));
+    }
+    units
+        .iter()
+        .find(|(name, _)| consume_name(unit, name) == Ok(b""))
+        .map(|(_, scale)| LengthPercentage::Length(value * scale))
+        .ok_or(())
+}
@@ -0,0 +1,246 @@
+use crate::gamut::RgbColorSpace;
+use crate::space::ColorSpace;
+use crate::{
+    normalize_hue, DisplayP3, Hsl, Hwb, Lab, Lch, LinearSrgb, Oklab, Oklch, Rec2020, Srgb, XyzD50,
+    XyzD65,
+};
+
+/// The color space in which colors are interpolated.
+///
+/// <https://www.w3.org/TR/css-color-4/#interpolation-space>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterpolationSpace {
+    /// `srgb`.
+    Srgb,
+    /// `srgb-linear`.
+    SrgbLinear,
+    /// `display-p3`.
+    DisplayP3,
+    /// `rec2020`.
+    Rec2020,
+    /// `lab`.
+    Lab,
+    /// `oklab`, the default.
+    Oklab,
+    /// `xyz-d50`.
+    XyzD50,
+    /// `xyz-d65` or `xyz`.
+    XyzD65,
+    /// `hsl`.
+    Hsl,
+    /// `hwb`.
+    Hwb,
+    /// `lch`.
+    Lch,
+    /// `oklch`.
+    Oklch,
+}
+
+impl Default for InterpolationSpace {
+    fn default() -> Self {
+        InterpolationSpace::Oklab
+    }
+}
+
+/// How hues are interpolated in cylindrical color spaces.
+///
+/// <https://www.w3.org/TR/css-color-4/#hue-interpolation>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HueInterpolation {
+    /// Takes the shorter arc between the two hues.
+    Shorter,
+    /// Takes the longer arc between the two hues.
+    Longer,
+    /// Moves from the first hue to the second hue in the direction of increasing angle.
+    Increasing,
+    /// Moves from the first hue to the second hue in the direction of decreasing angle.
+    Decreasing,
+}
+
+impl Default for HueInterpolation {
+    fn default() -> Self {
+        HueInterpolation::Shorter
+    }
+}
+
+/// A color converted to an interpolation space.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Interpolant {
+    pub(crate) components: [f32; 3],
+    pub(crate) alpha: f32,
+    /// Whether the hue is missing. Powerless hues are treated as missing.
+    pub(crate) hue_missing: bool,
+}
+
+impl InterpolationSpace {
+    /// Returns the index of the hue component, if the space is cylindrical.
+    pub(crate) fn hue_index(self) -> Option<usize> {
+        match self {
+            InterpolationSpace::Hsl | InterpolationSpace::Hwb => Some(0),
+            InterpolationSpace::Lch | InterpolationSpace::Oklch => Some(2),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_interpolant(self, xyz: XyzD65) -> Interpolant {
+        fn rgb<T: RgbColorSpace>(color: T) -> Interpolant {
+            Interpolant {
+                components: color.rgb(),
+                alpha: color.alpha(),
+                hue_missing: false,
+            }
+        }
+        fn rectangular(components: [f32; 3], alpha: f32) -> Interpolant {
+            Interpolant {
+                components,
+                alpha,
+                hue_missing: false,
+            }
+        }
+        fn cylindrical(components: [f32; 3], alpha: f32, hue_missing: bool) -> Interpolant {
+            Interpolant {
+                components,
+                alpha,
+                hue_missing,
+            }
+        }
+
+        match self {
+            InterpolationSpace::Srgb => rgb(Srgb::from_xyz(xyz)),
+            InterpolationSpace::SrgbLinear => rgb(LinearSrgb::from_xyz(xyz)),
+            InterpolationSpace::DisplayP3 => rgb(DisplayP3::from_xyz(xyz)),
+            InterpolationSpace::Rec2020 => rgb(Rec2020::from_xyz(xyz)),
+            InterpolationSpace::Lab => {
+                let c = Lab::from_xyz(xyz);
+                rectangular([c.lightness, c.a, c.b], c.alpha)
+            }
+            InterpolationSpace::Oklab => {
+                let c = Oklab::from_xyz(xyz);
+                rectangular([c.lightness, c.a, c.b], c.alpha)
+            }
+            InterpolationSpace::XyzD50 => {
+                let c = XyzD50::from_xyz(xyz);
+                rectangular([c.x, c.y, c.z], c.alpha)
+            }
+            InterpolationSpace::XyzD65 => rectangular([xyz.x, xyz.y, xyz.z], xyz.alpha),
+            InterpolationSpace::Hsl => {
+                let c = Hsl::from_xyz(xyz);
+                let powerless = c.is_hue_powerless();
+                cylindrical([c.hue, c.saturation, c.lightness], c.alpha, powerless)
+            }
+            InterpolationSpace::Hwb => {
+                let c = Hwb::from_xyz(xyz);
+                let powerless = c.is_hue_powerless();
+                cylindrical([c.hue, c.whiteness, c.blackness], c.alpha, powerless)
+            }
+            InterpolationSpace::Lch => {
+                let c = Lch::from_xyz(xyz);
+                let powerless = c.is_hue_powerless();
+                cylindrical([c.lightness, c.chroma, c.hue], c.alpha, powerless)
+            }
+            InterpolationSpace::Oklch => {
+                let c = Oklch::from_xyz(xyz);
+                let powerless = c.is_hue_powerless();
+                cylindrical([c.lightness, c.chroma, c.hue], c.alpha, powerless)
+            }
+        }
+    }
+
+    pub(crate) fn components_to_xyz(self, components: [f32; 3], alpha: f32) -> XyzD65 {
+        let [c0, c1, c2] = components;
+        match self {
+            InterpolationSpace::Srgb => Srgb::new(c0, c1, c2, alpha).to_xyz(),
+            InterpolationSpace::SrgbLinear => LinearSrgb::new(c0, c1, c2, alpha).to_xyz(),
+            InterpolationSpace::DisplayP3 => DisplayP3::new(c0, c1, c2, alpha).to_xyz(),
+            InterpolationSpace::Rec2020 => Rec2020::new(c0, c1, c2, alpha).to_xyz(),
+            InterpolationSpace::Lab => Lab::new(c0, c1, c2, alpha).to_xyz(),
+            InterpolationSpace::Oklab => Oklab::new(c0, c1, c2, alpha).to_xyz(),
+            InterpolationSpace::XyzD50 => XyzD50::new(c0, c1, c2, alpha).to_xyz(),
+            InterpolationSpace::XyzD65 => XyzD65::new(c0, c1, c2, alpha),
+            InterpolationSpace::Hsl => Hsl::new(c0, c1, c2, alpha).to_xyz(),
+            InterpolationSpace::Hwb => Hwb::new(c0, c1, c2, alpha).to_xyz(),
+            InterpolationSpace::Lch => Lch::new(c0, c1, c2, alpha).to_xyz(),
+            InterpolationSpace::Oklch => Oklch::new(c0, c1, c2, alpha).to_xyz(),
+        }
+    }
+}
+
+/// Interpolates between two colors.
+///
+/// <https://www.w3.org/TR/css-color-4/#interpolation>
+pub(crate) fn interpolate<T: ColorSpace>(
+    from: T,
+    to: T,
+    t: f32,
+    space: InterpolationSpace,
+    hue: HueInterpolation,
+) -> T {
+    let hue_index = space.hue_index();
+    let mut a = space.to_interpolant(from.to_xyz());
+    let mut b = space.to_interpolant(to.to_xyz());
+
+    // A powerless hue takes the hue of the other color. No other component is ever missing.
+    if let Some(i) = hue_index {
+        match (a.hue_missing, b.hue_missing) {
+            (true, false) => a.components[i] = b.components[i],
+            (false, true) => b.components[i] = a.components[i],
+            _ => {}
+        }
+        let (h1, h2) = fixup_hues(a.components[i], b.components[i], hue);
+        a.components[i] = h1;
+        b.components[i] = h2;
+    }
+
+    let alpha = lerp(a.alpha, b.alpha, t);
+    let mut components = [0.; 3];
+    for (i, component) in components.iter_mut().enumerate() {
+        if Some(i) == hue_index {
+            *component = normalize_hue(lerp(a.components[i], b.components[i], t));
+        } else {
+            let value = lerp(a.components[i] * a.alpha, b.components[i] * b.alpha, t);
+            *component = if alpha == 0. { value } else { value / alpha };
+        }
+    }
+
+    T::from_xyz(space.components_to_xyz(components, alpha))
+}
+
+/// Adjusts two hues in degrees so that linear interpolation between them follows the arc
+/// selected by `method`.
+///
+/// <https://www.w3.org/TR/css-color-4/#hue-interpolation>
+pub(crate) fn fixup_hues(h1: f32, h2: f32, method: HueInterpolation) -> (f32, f32) {
+    let (mut h1, mut h2) = (normalize_hue(h1), normalize_hue(h2));
+    let delta = h2 - h1;
+    match method {
+        HueInterpolation::Shorter => {
+            if delta > 180. {
+                h1 += 360.;
+            } else if delta < -180. {
+                h2 += 360.;
+            }
+        }
+        HueInterpolation::Longer => {
+            if 0. < delta && delta < 180. {
+                h1 += 360.;
+            } else if -180. < delta && delta <= 0. {
+                h2 += 360.;
+            }
+        }
+        HueInterpolation::Increasing => {
+            if delta < 0. {
+                h2 += 360.;
+            }
+        }
+        HueInterpolation::Decreasing => {
+            if delta > 0. {
+                h1 += 360.;
+            }
+        }
+    }
+    (h1, h2)
+}
+
+pub(crate) fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
@@ -0,0 +1,178 @@
+use crate::space::ColorSpace;
+use crate::{normalize_hue, XyzD50, XyzD65};
+
+/// A color in the CIE Lab color space with a D50 white point.
+///
+/// <https://www.w3.org/TR/css-color-4/#cie-lab>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lab {
+    /// The lightness component, from 0 to 100.
+    pub lightness: f32,
+    /// The a component.
+    pub a: f32,
+    /// The b component.
+    pub b: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+/// A color in the CIE LCH color space, the polar form of [`Lab`].
+///
+/// <https://www.w3.org/TR/css-color-4/#cie-lab>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lch {
+    /// The lightness component, from 0 to 100.
+    pub lightness: f32,
+    /// The chroma component.
+    pub chroma: f32,
+    /// The hue component in degrees.
+    pub hue: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+impl Lab {
+    pub fn new(lightness: f32, a: f32, b: f32, alpha: f32) -> Lab {
+        Lab {
+            lightness,
+            a,
+            b,
+            alpha,
+        }
+    }
+}
+
+impl Lch {
+    pub fn new(lightness: f32, chroma: f32, hue: f32, alpha: f32) -> Lch {
+        Lch {
+            lightness,
+            chroma,
+            hue,
+            alpha,
+        }
+    }
+
+    /// Returns `true` if the hue has no effect on the color.
+    pub fn is_hue_powerless(&self) -> bool {
+        self.chroma <= LCH_ACHROMATIC_THRESHOLD
+    }
+}
+
+const LCH_ACHROMATIC_THRESHOLD: f32 = 0.0015;
+
+const D50_WHITE: [f64; 3] = [0.3457 / 0.3585, 1., (1. - 0.3457 - 0.3585) / 0.3585];
+const EPSILON: f64 = 216. / 24389.;
+const KAPPA: f64 = 24389. / 27.;
+
+// https://www.w3.org/TR/css-color-4/#color-conversion-code
+impl From<XyzD50> for Lab {
+    fn from(xyz: XyzD50) -> Self {
+        let f = |value: f32, white: f64| {
+            let value = value as f64 / white;
+            if value > EPSILON {
+                value.cbrt()
+            } else {
+                (KAPPA * value + 16.) / 116.
+            }
+        };
+        let fx = f(xyz.x, D50_WHITE[0]);
+        let fy = f(xyz.y, D50_WHITE[1]);
+        let fz = f(xyz.z, D50_WHITE[2]);
+        Lab {
+            lightness: (116. * fy - 16.) as f32,
+            a: (500. * (fx - fy)) as f32,
+            b: (200. * (fy - fz)) as f32,
+            alpha: xyz.alpha,
+        }
+    }
+}
+
+impl From<Lab> for XyzD50 {
+    fn from(lab: Lab) -> Self {
+        let lightness = lab.lightness as f64;
+        let fy = (lightness + 16.) / 116.;
+        let fx = lab.a as f64 / 500. + fy;
+        let fz = fy - lab.b as f64 / 200.;
+        let f_inv = |f: f64| {
+            if f.powi(3) > EPSILON {
+                f.powi(3)
+            } else {
+                (116. * f - 16.) / KAPPA
+            }
+        };
+        let y = if lightness > KAPPA * EPSILON {
+            fy.powi(3)
+        } else {
+            lightness / KAPPA
+        };
+        XyzD50 {
+            x: (f_inv(fx) * D50_WHITE[0]) as f32,
+            y: (y * D50_WHITE[1]) as f32,
+            z: (f_inv(fz) * D50_WHITE[2]) as f32,
+            alpha: lab.alpha,
+        }
+    }
+}
+
+impl From<Lab> for Lch {
+    fn from(lab: Lab) -> Self {
+        let (chroma, hue) = to_polar(lab.a, lab.b, LCH_ACHROMATIC_THRESHOLD);
+        Lch {
+            lightness: lab.lightness,
+            chroma,
+            hue,
+            alpha: lab.alpha,
+        }
+    }
+}
+
+impl From<Lch> for Lab {
+    fn from(lch: Lch) -> Self {
+        let (a, b) = from_polar(lch.chroma, lch.hue);
+        Lab {
+            lightness: lch.lightness,
+            a,
+            b,
+            alpha: lch.alpha,
+        }
+    }
+}
+
+/// Converts rectangular coordinates to chroma and hue in degrees.
+///
+/// The hue is zero if the chroma does not exceed the achromatic threshold.
+pub(crate) fn to_polar(a: f32, b: f32, threshold: f32) -> (f32, f32) {
+    let (a, b) = (a as f64, b as f64);
+    let chroma = a.hypot(b) as f32;
+    if chroma <= threshold {
+        (chroma, 0.)
+    } else {
+        (chroma, normalize_hue(b.atan2(a).to_degrees() as f32))
+    }
+}
+
+/// Converts chroma and hue in degrees to rectangular coordinates.
+pub(crate) fn from_polar(chroma: f32, hue: f32) -> (f32, f32) {
+    let (sin, cos) = (hue as f64).to_radians().sin_cos();
+    ((chroma as f64 * cos) as f32, (chroma as f64 * sin) as f32)
+}
+
+impl ColorSpace for Lab {
+    fn to_xyz(self) -> XyzD65 {
+        XyzD65::from(XyzD50::from(self))
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        Lab::from(XyzD50::from(xyz))
+    }
+}
+
+impl ColorSpace for Lch {
+    fn to_xyz(self) -> XyzD65 {
+        Lab::from(self).to_xyz()
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        Lch::from(Lab::from_xyz(xyz))
+    }
+}
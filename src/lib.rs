@@ -3,9 +3,58 @@
 #![allow(clippy::len_zero)]
 #![cfg_attr(feature = "bench", feature(test))]
 
-use std::f32;
+use std::fmt;
 use std::str::{self, FromStr};
 
+mod cam16;
+pub mod colormap;
+mod composite;
+mod contrast;
+mod cvd;
+mod difference;
+mod filter;
+mod format;
+mod gamut;
+mod gradient;
+mod interpolate;
+mod lab;
+mod linear;
+pub mod material;
+pub mod named;
+mod oklab;
+mod predefined;
+mod premultiplied;
+mod preprocessor;
+mod scale;
+mod space;
+mod xyz;
+
+pub use cam16::{Cam16, Hct};
+pub use composite::{BlendMode, BlendSpace, CompositeOperator};
+pub use contrast::{
+    apca_contrast, apca_font_size, contrast_ratio, ContrastTarget, TextSize, WcagLevel,
+};
+pub use cvd::ColorVisionDeficiency;
+pub use difference::DeltaE;
+pub use filter::Filter;
+pub use format::{ColorFormat, Notation};
+pub use gamut::RgbColorSpace;
+pub use gradient::{
+    ColorStop, Gradient, GradientKind, LengthPercentage, LinearDirection, Position, RadialShape,
+    RadialSize,
+};
+pub use interpolate::{HueInterpolation, InterpolationSpace};
+pub use lab::{Lab, Lch};
+pub use linear::LinearSrgb;
+use named::NAMED_COLORS;
+pub use oklab::{Oklab, Oklch};
+pub use predefined::{DisplayP3, PredefinedSpace, Rec2020};
+pub use premultiplied::{PremultipliedLinearSrgb, PremultipliedSrgb};
+pub use preprocessor::{ColorChannels, Preprocessor};
+pub use scale::{ColorScale, ScaleInterpolation};
+pub use space::ColorSpace;
+pub use xyz::{XyzD50, XyzD65};
+
 const NONE: f32 = 0_f32;
 
 #[doc(hidden)]
@@ -34,11 +83,13 @@ impl Srgb {
         }
     }
 
-    fn from_rgb8(red: u8, green: u8, blue: u8) -> Srgb {
+    /// Creates an opaque color from 8-bit components.
+    pub fn from_rgb8(red: u8, green: u8, blue: u8) -> Srgb {
         Srgb::from_rgba8(red, green, blue, 255)
     }
 
-    fn from_rgba8(red: u8, green: u8, blue: u8, alpha: u8) -> Srgb {
+    /// Creates a color from 8-bit components.
+    pub fn from_rgba8(red: u8, green: u8, blue: u8, alpha: u8) -> Srgb {
         Srgb {
             red: red as f32 / 255.,
             green: green as f32 / 255.,
@@ -48,6 +99,94 @@ impl Srgb {
     }
 }
 
+// https://www.w3.org/TR/cssom-1/#serializing-css-values
+impl fmt::Display for Srgb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let red = color_f32_to_u8(self.red);
+        let green = color_f32_to_u8(self.green);
+        let blue = color_f32_to_u8(self.blue);
+        let alpha = color_f32_to_u8(self.alpha);
+        if alpha == 255 {
+            write!(f, "rgb({}, {}, {})", red, green, blue)
+        } else {
+            write!(
+                f,
+                "rgba({}, {}, {}, {})",
+                red,
+                green,
+                blue,
+                serialize_alpha(alpha)
+            )
+        }
+    }
+}
+
+fn color_f32_to_u8(value: f32) -> u8 {
+    (value * 255.).round().clamp(0., 255.) as u8
+}
+
+fn to_rgba8(color: Srgb) -> [u8; 4] {
+    [
+        color_f32_to_u8(color.red),
+        color_f32_to_u8(color.green),
+        color_f32_to_u8(color.blue),
+        color_f32_to_u8(color.alpha),
+    ]
+}
+
+/// Returns the shortest decimal (at most three places) that maps back to the given 8-bit alpha.
+///
+/// <https://www.w3.org/TR/css-color-4/#serializing-alpha-values>
+fn serialize_alpha(alpha: u8) -> f32 {
+    let rounded = (alpha as f32 / 255. * 100.).round() / 100.;
+    if color_f32_to_u8(rounded) == alpha {
+        rounded
+    } else {
+        (alpha as f32 / 255. * 1000.).round() / 1000.
+    }
+}
+
+/// How to choose between named colors that are aliases of each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NamedAlias {
+    /// Prefer `aqua`, `fuchsia` and the `gray` spellings.
+    Primary,
+    /// Prefer `cyan`, `magenta` and the `grey` spellings.
+    Secondary,
+}
+
+impl Srgb {
+    /// Returns the named color matching this color, if any.
+    ///
+    /// Colors are compared by their 8-bit values. The `transparent` keyword is not considered.
+    /// Aliases are resolved with [`NamedAlias::Primary`].
+    pub fn to_named(&self) -> Option<&'static str> {
+        self.to_named_with(NamedAlias::Primary)
+    }
+
+    /// Returns the named color matching this color, if any, resolving aliases with the given
+    /// policy.
+    pub fn to_named_with(&self, alias: NamedAlias) -> Option<&'static str> {
+        let rgba8 = to_rgba8(*self);
+        let mut matches = NAMED_COLORS
+            .iter()
+            .filter(|(_, color)| to_rgba8(*color) == rgba8)
+            .map(|(name, _)| *name);
+        // The table is sorted, so `aqua`, `fuchsia` and `gray` come before their aliases.
+        match alias {
+            NamedAlias::Primary => matches.next(),
+            NamedAlias::Secondary => matches.next_back(),
+        }
+    }
+}
+
+/// Returns an iterator over all named colors in alphabetical order.
+///
+/// Aliases such as `gray` and `grey` are both included. The `transparent` keyword is not.
+pub fn named_colors() -> impl ExactSizeIterator<Item = (&'static str, Srgb)> {
+    NAMED_COLORS.iter().copied()
+}
+
 #[derive(Debug)]
 pub struct ParseColorError;
 
@@ -59,49 +198,121 @@ impl FromStr for Srgb {
     }
 }
 
-// https://www.w3.org/TR/css-color-4/
+/// Options for parsing colors.
+///
+/// The default options match [`FromStr`] for [`Srgb`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Whether to keep components outside of the sRGB gamut.
+    ///
+    /// By default, the components of `rgb()`, `hsl()` and `hwb()` are clamped when parsed and
+    /// `color()` values are gamut mapped to sRGB. With an extended range, the components are kept
+    /// as specified so that they can be gamut mapped or clamped later, for example with
+    /// [`ColorSpace::to_gamut`] or [`RgbColorSpace::clip`]. The alpha component is always clamped.
+    pub extended_range: bool,
+}
+
+impl ParseOptions {
+    /// Parses a color with these options.
+    pub fn parse(&self, s: &str) -> Result<Srgb, ParseColorError> {
+        parse_color(s.as_bytes(), !self.extended_range).map_err(|_| ParseColorError)
+    }
+}
+
 fn parse_css_color(input: &[u8]) -> Result<Srgb, ()> {
+    parse_color(input, true)
+}
+
+// https://www.w3.org/TR/css-color-4/
+fn parse_color(input: &[u8], clamp: bool) -> Result<Srgb, ()> {
     if let Ok(input) = consume_byte(input, b'#') {
         parse_hex(input)
     } else if let Ok(input) = consume_function(input, b"rgb") {
-        parse_rgb(input)
+        parse_rgb(input, clamp)
     } else if let Ok(input) = consume_function(input, b"rgba") {
-        parse_rgb(input)
+        parse_rgb(input, clamp)
     } else if let Ok(input) = consume_function(input, b"hsl") {
-        parse_hsl(input)
+        parse_hsl(input, clamp).map(Srgb::from)
     } else if let Ok(input) = consume_function(input, b"hsla") {
-        parse_hsl(input)
+        parse_hsl(input, clamp).map(Srgb::from)
     } else if let Ok(input) = consume_function(input, b"hwb") {
-        parse_hwb(input)
+        parse_hwb(input, clamp).map(Srgb::from)
+    } else if let Ok(input) = consume_function(input, b"color") {
+        parse_color_function(input, clamp)
     } else {
         parse_named(input)
     }
 }
 
+// Unlike `clamp`, this maps NaN to 0.
+#[allow(clippy::manual_clamp)]
 fn clamp_unit_f32(value: f32) -> f32 {
     value.max(0.).min(1.)
 }
 
 fn normalize_hue(value: f32) -> f32 {
-    value - value.floor()
+    let value = value.rem_euclid(360.);
+    // Rounding can produce exactly 360 for tiny negative values.
+    if value < 360. {
+        value
+    } else {
+        0.
+    }
 }
 
-struct Hsla {
+/// A color in the HSL notation of the sRGB color space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsl {
+    /// The hue component in degrees.
     pub hue: f32,
+    /// The saturation component.
     pub saturation: f32,
+    /// The lightness component.
     pub lightness: f32,
+    /// The alpha component.
     pub alpha: f32,
 }
 
+impl Hsl {
+    pub fn new(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Hsl {
+        Hsl {
+            hue,
+            saturation,
+            lightness,
+            alpha,
+        }
+    }
+
+    /// Returns `true` if the hue has no effect on the color.
+    ///
+    /// <https://www.w3.org/TR/css-color-4/#hsl-to-rgb>
+    pub fn is_hue_powerless(&self) -> bool {
+        self.saturation <= 0. || self.lightness <= 0. || self.lightness >= 1.
+    }
+}
+
+impl FromStr for Hsl {
+    type Err = ParseColorError;
+
+    /// Parses the `hsl()` and `hsla()` functions only.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.as_bytes();
+        consume_function(input, b"hsl")
+            .or_else(|_| consume_function(input, b"hsla"))
+            .and_then(|input| parse_hsl(input, true))
+            .map_err(|_| ParseColorError)
+    }
+}
+
 // https://www.w3.org/TR/css-color-4/#hsl-to-rgb
-impl From<Hsla> for Srgb {
-    fn from(hsla: Hsla) -> Self {
-        let t2 = if hsla.lightness <= 0.5 {
-            hsla.lightness * (hsla.saturation + 1.)
+impl From<Hsl> for Srgb {
+    fn from(hsl: Hsl) -> Self {
+        let t2 = if hsl.lightness <= 0.5 {
+            hsl.lightness * (hsl.saturation + 1.)
         } else {
-            hsla.lightness + hsla.saturation - hsla.lightness * hsla.saturation
+            hsl.lightness + hsl.saturation - hsl.lightness * hsl.saturation
         };
-        let t1 = hsla.lightness * 2. - t2;
+        let t1 = hsl.lightness * 2. - t2;
 
         let hue_to_rgb = |h6: f32| -> f32 {
             if h6 < 1. {
@@ -114,37 +325,108 @@ impl From<Hsla> for Srgb {
                 t1
             }
         };
-        let h6 = hsla.hue * 6.;
+        let h6 = normalize_hue(hsl.hue) / 60.;
         let h6_red = if h6 + 2. < 6. { h6 + 2. } else { h6 - 4. };
         let h6_blue = if h6 - 2. >= 0. { h6 - 2. } else { h6 + 4. };
         Srgb {
             red: hue_to_rgb(h6_red),
             green: hue_to_rgb(h6),
             blue: hue_to_rgb(h6_blue),
-            alpha: hsla.alpha,
+            alpha: hsl.alpha,
         }
     }
 }
 
-struct Hwba {
+// https://www.w3.org/TR/css-color-4/#rgb-to-hsl
+impl From<Srgb> for Hsl {
+    fn from(srgb: Srgb) -> Self {
+        let max = srgb.red.max(srgb.green).max(srgb.blue);
+        let min = srgb.red.min(srgb.green).min(srgb.blue);
+        let lightness = (min + max) / 2.;
+        let d = max - min;
+        let saturation = if d == 0. || lightness <= 0. || lightness >= 1. {
+            0.
+        } else {
+            (max - lightness) / lightness.min(1. - lightness)
+        };
+        Hsl {
+            hue: rgb_to_hue(srgb.red, srgb.green, srgb.blue, max, d),
+            saturation,
+            lightness,
+            alpha: srgb.alpha,
+        }
+    }
+}
+
+/// Returns the hue of an RGB color in degrees, or zero if the color is achromatic.
+fn rgb_to_hue(red: f32, green: f32, blue: f32, max: f32, d: f32) -> f32 {
+    if d == 0. {
+        return 0.;
+    }
+    let h6 = if max == red {
+        (green - blue) / d + if green < blue { 6. } else { 0. }
+    } else if max == green {
+        (blue - red) / d + 2.
+    } else {
+        (red - green) / d + 4.
+    };
+    normalize_hue(h6 * 60.)
+}
+
+/// A color in the HWB notation of the sRGB color space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hwb {
+    /// The hue component in degrees.
     pub hue: f32,
+    /// The whiteness component.
     pub whiteness: f32,
+    /// The blackness component.
     pub blackness: f32,
+    /// The alpha component.
     pub alpha: f32,
 }
 
+impl Hwb {
+    pub fn new(hue: f32, whiteness: f32, blackness: f32, alpha: f32) -> Hwb {
+        Hwb {
+            hue,
+            whiteness,
+            blackness,
+            alpha,
+        }
+    }
+
+    /// Returns `true` if the hue has no effect on the color.
+    ///
+    /// <https://www.w3.org/TR/css-color-4/#hwb-to-rgb>
+    pub fn is_hue_powerless(&self) -> bool {
+        self.whiteness + self.blackness >= 1.
+    }
+}
+
+impl FromStr for Hwb {
+    type Err = ParseColorError;
+
+    /// Parses the `hwb()` function only.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        consume_function(s.as_bytes(), b"hwb")
+            .and_then(|input| parse_hwb(input, true))
+            .map_err(|_| ParseColorError)
+    }
+}
+
 // https://www.w3.org/TR/css-color-4/#hwb-to-rgb
-impl From<Hwba> for Srgb {
-    fn from(hwba: Hwba) -> Self {
+impl From<Hwb> for Srgb {
+    fn from(hwb: Hwb) -> Self {
         // If the sum of these two arguments is greater than 100%, then at computed-value time they
         // are further normalized to add up to 100%, with the same relative ratio.
-        if hwba.whiteness + hwba.blackness >= 1. {
-            let gray = hwba.whiteness / (hwba.whiteness + hwba.blackness);
+        if hwb.whiteness + hwb.blackness >= 1. {
+            let gray = hwb.whiteness / (hwb.whiteness + hwb.blackness);
             Srgb {
                 red: gray,
                 green: gray,
                 blue: gray,
-                alpha: hwba.alpha,
+                alpha: hwb.alpha,
             }
         } else {
             fn hue_to_rgb(h6: f32) -> f32 {
@@ -158,20 +440,34 @@ impl From<Hwba> for Srgb {
                     0.
                 }
             }
-            let h6 = hwba.hue * 6.;
+            let h6 = normalize_hue(hwb.hue) / 60.;
             let h6_red = if h6 + 2. < 6. { h6 + 2. } else { h6 - 4. };
             let h6_blue = if h6 - 2. >= 0. { h6 - 2. } else { h6 + 4. };
-            let x = 1. - hwba.whiteness - hwba.blackness;
+            let x = 1. - hwb.whiteness - hwb.blackness;
             Srgb {
-                red: hue_to_rgb(h6_red) * x + hwba.whiteness,
-                green: hue_to_rgb(h6) * x + hwba.whiteness,
-                blue: hue_to_rgb(h6_blue) * x + hwba.whiteness,
-                alpha: hwba.alpha,
+                red: hue_to_rgb(h6_red) * x + hwb.whiteness,
+                green: hue_to_rgb(h6) * x + hwb.whiteness,
+                blue: hue_to_rgb(h6_blue) * x + hwb.whiteness,
+                alpha: hwb.alpha,
             }
         }
     }
 }
 
+// https://www.w3.org/TR/css-color-4/#rgb-to-hwb
+impl From<Srgb> for Hwb {
+    fn from(srgb: Srgb) -> Self {
+        let max = srgb.red.max(srgb.green).max(srgb.blue);
+        let min = srgb.red.min(srgb.green).min(srgb.blue);
+        Hwb {
+            hue: rgb_to_hue(srgb.red, srgb.green, srgb.blue, max, max - min),
+            whiteness: min,
+            blackness: 1. - max,
+            alpha: srgb.alpha,
+        }
+    }
+}
+
 fn is_ident_start(input: &[u8]) -> bool {
     match input.get(0) {
         Some(b'-') => match input.get(1) {
@@ -358,19 +654,22 @@ fn parse_alpha_value(input: &[u8]) -> Result<(&[u8], f32), ()> {
 }
 
 // <hue> = <number> | <angle>
+//
+// Returns the hue in degrees. Units are converted in double precision so that common angles map
+// exactly onto whole degrees.
 fn parse_hue(input: &[u8]) -> Result<(&[u8], f32), ()> {
     let (input, value) = parse_number(input)?;
 
     if !is_ident_start(input) {
-        Ok((input, value / 360.))
+        Ok((input, value))
     } else if let Ok(input) = consume_name(input, b"deg") {
-        Ok((input, value / 360.))
+        Ok((input, value))
     } else if let Ok(input) = consume_name(input, b"grad") {
-        Ok((input, value / 400.))
+        Ok((input, (value as f64 * 0.9) as f32))
     } else if let Ok(input) = consume_name(input, b"rad") {
-        Ok((input, value / (2. * f32::consts::PI)))
+        Ok((input, (value as f64).to_degrees() as f32))
     } else if let Ok(input) = consume_name(input, b"turn") {
-        Ok((input, value))
+        Ok((input, (value as f64 * 360.) as f32))
     } else {
         Err(())
     }
@@ -417,7 +716,7 @@ fn parse_hex(input: &[u8]) -> Result<Srgb, ()> {
 //                              [<percentage> | <number> | none]
 //                              [<percentage> | <number> | none]
 //                              [ / [<alpha-value> | none] ]? )
-fn parse_hsl(input: &[u8]) -> Result<Srgb, ()> {
+fn parse_hsl(input: &[u8], clamp: bool) -> Result<Hsl, ()> {
     let (input, hue, legacy_syntax) = if let Ok((input, hue)) = parse_hue(input) {
         let input = skip_ws(input);
         match input.get(0) {
@@ -468,19 +767,28 @@ fn parse_hsl(input: &[u8]) -> Result<Srgb, ()> {
         return Err(());
     }
 
-    Ok(Srgb::from(Hsla {
+    let hsl = Hsl {
         hue: normalize_hue(hue),
-        saturation: clamp_unit_f32(saturation),
-        lightness: clamp_unit_f32(lightness),
+        saturation,
+        lightness,
         alpha,
-    }))
+    };
+    if clamp {
+        Ok(Hsl {
+            saturation: clamp_unit_f32(saturation),
+            lightness: clamp_unit_f32(lightness),
+            ..hsl
+        })
+    } else {
+        Ok(hsl)
+    }
 }
 
 // hwb() = hwb( [<hue> | none]
 //              [<percentage> | <number> | none]
 //              [<percentage> | <number> | none]
 //              [ / [<alpha-value> | none] ]? )
-fn parse_hwb(input: &[u8]) -> Result<Srgb, ()> {
+fn parse_hwb(input: &[u8], clamp: bool) -> Result<Hwb, ()> {
     let (input, hue) = if let Ok((input, hue)) = parse_hue(input) {
         (skip_ws(input), hue)
     } else {
@@ -515,12 +823,21 @@ fn parse_hwb(input: &[u8]) -> Result<Srgb, ()> {
         return Err(());
     }
 
-    Ok(Srgb::from(Hwba {
+    let hwb = Hwb {
         hue: normalize_hue(hue),
-        whiteness: clamp_unit_f32(whiteness),
-        blackness: clamp_unit_f32(blackness),
+        whiteness,
+        blackness,
         alpha,
-    }))
+    };
+    if clamp {
+        Ok(Hwb {
+            whiteness: clamp_unit_f32(whiteness),
+            blackness: clamp_unit_f32(blackness),
+            ..hwb
+        })
+    } else {
+        Ok(hwb)
+    }
 }
 
 // rgb()  = [ <legacy-rgb-syntax>  | <modern-rgb-syntax>  ]
@@ -531,7 +848,7 @@ fn parse_hwb(input: &[u8]) -> Result<Srgb, ()> {
 //                        rgba( <number>#{3}     , <alpha-value>? )
 // <modern-rgb-syntax>  = rgb(  [ <number> | <percentage> | none]{3} [ / [<alpha-value> | none] ]? )
 // <modern-rgba-syntax> = rgba( [ <number> | <percentage> | none]{3} [ / [<alpha-value> | none] ]? )
-fn parse_rgb(input: &[u8]) -> Result<Srgb, ()> {
+fn parse_rgb(input: &[u8], clamp: bool) -> Result<Srgb, ()> {
     let (input, red, legacy_syntax) = if let Ok((input, red)) = parse_number_or_percentage(input) {
         let input = skip_ws(input);
         match input.get(0) {
@@ -594,18 +911,79 @@ fn parse_rgb(input: &[u8]) -> Result<Srgb, ()> {
         return Err(());
     }
 
-    Ok(Srgb::new(
-        clamp_unit_f32(red),
-        clamp_unit_f32(green),
-        clamp_unit_f32(blue),
-        alpha,
-    ))
+    let srgb = Srgb::new(red, green, blue, alpha);
+    if clamp {
+        Ok(srgb.clip())
+    } else {
+        Ok(srgb)
+    }
 }
 
-macro_rules! rgb {
-    ($red: expr, $green: expr, $blue: expr) => {
-        Srgb::from_rgb8($red, $green, $blue)
+// color() = color( <colorspace-params> [ / [ <alpha-value> | none ] ]? )
+// <colorspace-params> = [ <predefined-rgb-params> | <xyz-params> ]
+// <predefined-rgb-params> = <predefined-rgb> [ <number> | <percentage> | none ]{3}
+// <predefined-rgb> = srgb | srgb-linear | display-p3 | a98-rgb | prophoto-rgb | rec2020
+// <xyz-params> = <xyz-space> [ <number> | <percentage> | none ]{3}
+// <xyz-space> = xyz | xyz-d50 | xyz-d65
+//
+// The a98-rgb and prophoto-rgb color spaces are not supported.
+fn parse_color_function(input: &[u8], clamp: bool) -> Result<Srgb, ()> {
+    let spaces = [
+        (&b"srgb"[..], PredefinedSpace::Srgb),
+        (b"srgb-linear", PredefinedSpace::SrgbLinear),
+        (b"display-p3", PredefinedSpace::DisplayP3),
+        (b"rec2020", PredefinedSpace::Rec2020),
+        (b"xyz", PredefinedSpace::XyzD65),
+        (b"xyz-d50", PredefinedSpace::XyzD50),
+        (b"xyz-d65", PredefinedSpace::XyzD65),
+    ];
+    let (mut input, space) = spaces
+        .iter()
+        .find_map(|(name, space)| {
+            consume_name(input, name)
+                .ok()
+                .map(|input| (skip_ws(input), *space))
+        })
+        .ok_or(())?;
+
+    let mut components = [NONE; 3];
+    for component in components.iter_mut() {
+        input = if let Ok((input, value)) = parse_number_or_percentage(input) {
+            *component = value.frac(1.);
+            skip_ws(input)
+        } else {
+            skip_ws(consume_none(input)?)
+        };
+    }
+
+    let (input, alpha) = match input.get(0) {
+        Some(b'/') => {
+            let input = skip_ws(&input[1..]);
+            if let Ok((input, alpha)) = parse_alpha_value(input) {
+                (skip_ws(input), alpha)
+            } else {
+                (skip_ws(consume_none(input)?), NONE)
+            }
+        }
+        _ => (input, 1.),
     };
+
+    if input != b")" {
+        return Err(());
+    }
+
+    let srgb = space.to_srgb(components, alpha);
+    let finite = [srgb.red, srgb.green, srgb.blue]
+        .iter()
+        .all(|c| c.is_finite());
+    if !clamp {
+        Ok(srgb)
+    } else if srgb.is_in_gamut() || !finite {
+        // Gamut mapping is undefined for infinite and NaN components, so they are clipped.
+        Ok(srgb.clip())
+    } else {
+        Ok(srgb.to_gamut())
+    }
 }
 
 fn parse_named(input: &[u8]) -> Result<Srgb, ()> {
@@ -618,158 +996,13 @@ fn parse_named(input: &[u8]) -> Result<Srgb, ()> {
     for (i, c) in input.iter().enumerate() {
         name[i] = c.to_ascii_lowercase();
     }
-    Ok(match &*name {
-        b"aliceblue" => rgb!(240, 248, 255),
-        b"antiquewhite" => rgb!(250, 235, 215),
-        b"aqua" => rgb!(0, 255, 255),
-        b"aquamarine" => rgb!(127, 255, 212),
-        b"azure" => rgb!(240, 255, 255),
-        b"beige" => rgb!(245, 245, 220),
-        b"bisque" => rgb!(255, 228, 196),
-        b"black" => rgb!(0, 0, 0),
-        b"blanchedalmond" => rgb!(255, 235, 205),
-        b"blue" => rgb!(0, 0, 255),
-        b"blueviolet" => rgb!(138, 43, 226),
-        b"brown" => rgb!(165, 42, 42),
-        b"burlywood" => rgb!(222, 184, 135),
-        b"cadetblue" => rgb!(95, 158, 160),
-        b"chartreuse" => rgb!(127, 255, 0),
-        b"chocolate" => rgb!(210, 105, 30),
-        b"coral" => rgb!(255, 127, 80),
-        b"cornflowerblue" => rgb!(100, 149, 237),
-        b"cornsilk" => rgb!(255, 248, 220),
-        b"crimson" => rgb!(220, 20, 60),
-        b"cyan" => rgb!(0, 255, 255),
-        b"darkblue" => rgb!(0, 0, 139),
-        b"darkcyan" => rgb!(0, 139, 139),
-        b"darkgoldenrod" => rgb!(184, 134, 11),
-        b"darkgray" => rgb!(169, 169, 169),
-        b"darkgreen" => rgb!(0, 100, 0),
-        b"darkgrey" => rgb!(169, 169, 169),
-        b"darkkhaki" => rgb!(189, 183, 107),
-        b"darkmagenta" => rgb!(139, 0, 139),
-        b"darkolivegreen" => rgb!(85, 107, 47),
-        b"darkorange" => rgb!(255, 140, 0),
-        b"darkorchid" => rgb!(153, 50, 204),
-        b"darkred" => rgb!(139, 0, 0),
-        b"darksalmon" => rgb!(233, 150, 122),
-        b"darkseagreen" => rgb!(143, 188, 143),
-        b"darkslateblue" => rgb!(72, 61, 139),
-        b"darkslategray" => rgb!(47, 79, 79),
-        b"darkslategrey" => rgb!(47, 79, 79),
-        b"darkturquoise" => rgb!(0, 206, 209),
-        b"darkviolet" => rgb!(148, 0, 211),
-        b"deeppink" => rgb!(255, 20, 147),
-        b"deepskyblue" => rgb!(0, 191, 255),
-        b"dimgray" => rgb!(105, 105, 105),
-        b"dimgrey" => rgb!(105, 105, 105),
-        b"dodgerblue" => rgb!(30, 144, 255),
-        b"firebrick" => rgb!(178, 34, 34),
-        b"floralwhite" => rgb!(255, 250, 240),
-        b"forestgreen" => rgb!(34, 139, 34),
-        b"fuchsia" => rgb!(255, 0, 255),
-        b"gainsboro" => rgb!(220, 220, 220),
-        b"ghostwhite" => rgb!(248, 248, 255),
-        b"gold" => rgb!(255, 215, 0),
-        b"goldenrod" => rgb!(218, 165, 32),
-        b"gray" => rgb!(128, 128, 128),
-        b"green" => rgb!(0, 128, 0),
-        b"greenyellow" => rgb!(173, 255, 47),
-        b"grey" => rgb!(128, 128, 128),
-        b"honeydew" => rgb!(240, 255, 240),
-        b"hotpink" => rgb!(255, 105, 180),
-        b"indianred" => rgb!(205, 92, 92),
-        b"indigo" => rgb!(75, 0, 130),
-        b"ivory" => rgb!(255, 255, 240),
-        b"khaki" => rgb!(240, 230, 140),
-        b"lavender" => rgb!(230, 230, 250),
-        b"lavenderblush" => rgb!(255, 240, 245),
-        b"lawngreen" => rgb!(124, 252, 0),
-        b"lemonchiffon" => rgb!(255, 250, 205),
-        b"lightblue" => rgb!(173, 216, 230),
-        b"lightcoral" => rgb!(240, 128, 128),
-        b"lightcyan" => rgb!(224, 255, 255),
-        b"lightgoldenrodyellow" => rgb!(250, 250, 210),
-        b"lightgray" => rgb!(211, 211, 211),
-        b"lightgreen" => rgb!(144, 238, 144),
-        b"lightgrey" => rgb!(211, 211, 211),
-        b"lightpink" => rgb!(255, 182, 193),
-        b"lightsalmon" => rgb!(255, 160, 122),
-        b"lightseagreen" => rgb!(32, 178, 170),
-        b"lightskyblue" => rgb!(135, 206, 250),
-        b"lightslategray" => rgb!(119, 136, 153),
-        b"lightslategrey" => rgb!(119, 136, 153),
-        b"lightsteelblue" => rgb!(176, 196, 222),
-        b"lightyellow" => rgb!(255, 255, 224),
-        b"lime" => rgb!(0, 255, 0),
-        b"limegreen" => rgb!(50, 205, 50),
-        b"linen" => rgb!(250, 240, 230),
-        b"magenta" => rgb!(255, 0, 255),
-        b"maroon" => rgb!(128, 0, 0),
-        b"mediumaquamarine" => rgb!(102, 205, 170),
-        b"mediumblue" => rgb!(0, 0, 205),
-        b"mediumorchid" => rgb!(186, 85, 211),
-        b"mediumpurple" => rgb!(147, 112, 219),
-        b"mediumseagreen" => rgb!(60, 179, 113),
-        b"mediumslateblue" => rgb!(123, 104, 238),
-        b"mediumspringgreen" => rgb!(0, 250, 154),
-        b"mediumturquoise" => rgb!(72, 209, 204),
-        b"mediumvioletred" => rgb!(199, 21, 133),
-        b"midnightblue" => rgb!(25, 25, 112),
-        b"mintcream" => rgb!(245, 255, 250),
-        b"mistyrose" => rgb!(255, 228, 225),
-        b"moccasin" => rgb!(255, 228, 181),
-        b"navajowhite" => rgb!(255, 222, 173),
-        b"navy" => rgb!(0, 0, 128),
-        b"oldlace" => rgb!(253, 245, 230),
-        b"olive" => rgb!(128, 128, 0),
-        b"olivedrab" => rgb!(107, 142, 35),
-        b"orange" => rgb!(255, 165, 0),
-        b"orangered" => rgb!(255, 69, 0),
-        b"orchid" => rgb!(218, 112, 214),
-        b"palegoldenrod" => rgb!(238, 232, 170),
-        b"palegreen" => rgb!(152, 251, 152),
-        b"paleturquoise" => rgb!(175, 238, 238),
-        b"palevioletred" => rgb!(219, 112, 147),
-        b"papayawhip" => rgb!(255, 239, 213),
-        b"peachpuff" => rgb!(255, 218, 185),
-        b"peru" => rgb!(205, 133, 63),
-        b"pink" => rgb!(255, 192, 203),
-        b"plum" => rgb!(221, 160, 221),
-        b"powderblue" => rgb!(176, 224, 230),
-        b"purple" => rgb!(128, 0, 128),
-        b"rebeccapurple" => rgb!(102, 51, 153),
-        b"red" => rgb!(255, 0, 0),
-        b"rosybrown" => rgb!(188, 143, 143),
-        b"royalblue" => rgb!(65, 105, 225),
-        b"saddlebrown" => rgb!(139, 69, 19),
-        b"salmon" => rgb!(250, 128, 114),
-        b"sandybrown" => rgb!(244, 164, 96),
-        b"seagreen" => rgb!(46, 139, 87),
-        b"seashell" => rgb!(255, 245, 238),
-        b"sienna" => rgb!(160, 82, 45),
-        b"silver" => rgb!(192, 192, 192),
-        b"skyblue" => rgb!(135, 206, 235),
-        b"slateblue" => rgb!(106, 90, 205),
-        b"slategray" => rgb!(112, 128, 144),
-        b"slategrey" => rgb!(112, 128, 144),
-        b"snow" => rgb!(255, 250, 250),
-        b"springgreen" => rgb!(0, 255, 127),
-        b"steelblue" => rgb!(70, 130, 180),
-        b"tan" => rgb!(210, 180, 140),
-        b"teal" => rgb!(0, 128, 128),
-        b"thistle" => rgb!(216, 191, 216),
-        b"tomato" => rgb!(255, 99, 71),
-        b"turquoise" => rgb!(64, 224, 208),
-        b"violet" => rgb!(238, 130, 238),
-        b"wheat" => rgb!(245, 222, 179),
-        b"white" => rgb!(255, 255, 255),
-        b"whitesmoke" => rgb!(245, 245, 245),
-        b"yellow" => rgb!(255, 255, 0),
-        b"yellowgreen" => rgb!(154, 205, 50),
-        b"transparent" => Srgb::new(0., 0., 0., 0.),
-        _ => return Err(()),
-    })
+    if name == b"transparent" {
+        return Ok(named::TRANSPARENT);
+    }
+    match NAMED_COLORS.binary_search_by(|(key, _)| key.as_bytes().cmp(name)) {
+        Ok(i) => Ok(NAMED_COLORS[i].1),
+        Err(_) => Err(()),
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,88 @@
+use crate::space::ColorSpace;
+use crate::{Srgb, XyzD65};
+
+/// A color in the linear-light sRGB color space.
+///
+/// <https://www.w3.org/TR/css-color-4/#predefined-sRGB-linear>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearSrgb {
+    /// The red component.
+    pub red: f32,
+    /// The green component.
+    pub green: f32,
+    /// The blue component.
+    pub blue: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+impl LinearSrgb {
+    pub fn new(red: f32, green: f32, blue: f32, alpha: f32) -> LinearSrgb {
+        LinearSrgb {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+}
+
+impl From<Srgb> for LinearSrgb {
+    fn from(srgb: Srgb) -> Self {
+        LinearSrgb {
+            red: srgb_to_linear(srgb.red),
+            green: srgb_to_linear(srgb.green),
+            blue: srgb_to_linear(srgb.blue),
+            alpha: srgb.alpha,
+        }
+    }
+}
+
+impl From<LinearSrgb> for Srgb {
+    fn from(linear: LinearSrgb) -> Self {
+        Srgb {
+            red: linear_to_srgb(linear.red),
+            green: linear_to_srgb(linear.green),
+            blue: linear_to_srgb(linear.blue),
+            alpha: linear.alpha,
+        }
+    }
+}
+
+/// Converts a gamma-encoded sRGB component to linear light.
+///
+/// Values outside the unit interval are extended by reflection about zero.
+///
+/// <https://www.w3.org/TR/css-color-4/#color-conversion-code>
+pub(crate) fn srgb_to_linear(value: f32) -> f32 {
+    let value = value as f64;
+    let abs = value.abs();
+    let linear = if abs <= 0.04045 {
+        abs / 12.92
+    } else {
+        ((abs + 0.055) / 1.055).powf(2.4)
+    };
+    (linear.copysign(value)) as f32
+}
+
+/// Converts a linear-light sRGB component to gamma-encoded sRGB.
+pub(crate) fn linear_to_srgb(value: f32) -> f32 {
+    let value = value as f64;
+    let abs = value.abs();
+    let srgb = if abs <= 0.0031308 {
+        abs * 12.92
+    } else {
+        1.055 * abs.powf(1. / 2.4) - 0.055
+    };
+    (srgb.copysign(value)) as f32
+}
+
+impl ColorSpace for LinearSrgb {
+    fn to_xyz(self) -> XyzD65 {
+        XyzD65::from(self)
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        LinearSrgb::from(xyz)
+    }
+}
@@ -0,0 +1,1085 @@
+//! Material Design 3 dynamic color: tonal palettes and color schemes generated from a seed color.
+//!
+//! The results match Material Color Utilities, so themes generated here are identical to those of
+//! Android and the web.
+//!
+//! <https://github.com/material-foundation/material-color-utilities>
+
+use crate::cam16::{hct_from_rgb8, lab_from_rgb8, lstar_from_y, rgb8_from_hct, y_from_lstar};
+use crate::{to_rgba8, Srgb};
+use std::cmp::Ordering;
+
+/// A palette of colors with the same HCT hue and chroma and any tone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TonalPalette {
+    /// The HCT hue in degrees.
+    pub hue: f32,
+    /// The HCT chroma. Tones that cannot reach it in sRGB have the closest chroma possible.
+    pub chroma: f32,
+}
+
+impl TonalPalette {
+    pub fn new(hue: f32, chroma: f32) -> TonalPalette {
+        TonalPalette { hue, chroma }
+    }
+
+    /// Returns the color of a tone, from 0 for black to 100 for white, rounded to 8 bits.
+    pub fn tone(&self, tone: f32) -> Srgb {
+        let [red, green, blue] = self.color(f64::from(tone)).rgb;
+        Srgb::from_rgb8(red, green, blue)
+    }
+
+    fn color(&self, tone: f64) -> Color {
+        Color::from_hct(f64::from(self.hue), f64::from(self.chroma), tone)
+    }
+
+    fn from_color(color: Color) -> TonalPalette {
+        TonalPalette::new(color.hue as f32, color.chroma as f32)
+    }
+}
+
+impl From<Srgb> for TonalPalette {
+    /// Returns the palette with the hue and chroma of a color, rounded to 8 bits.
+    fn from(srgb: Srgb) -> Self {
+        TonalPalette::from_color(Color::from_srgb(srgb))
+    }
+}
+
+/// The style of a [`Scheme`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    /// The default Material theme: a calm primary color with muted secondary and tertiary colors.
+    TonalSpot,
+    /// Maximum colorfulness in the primary palette, with related secondary and tertiary hues.
+    Vibrant,
+    /// A playful theme whose primary hue is deliberately not the seed's hue.
+    Expressive,
+    /// Keeps the seed's hue and chroma, for colors taken from content such as images.
+    Content,
+    /// Like `Content`, with a tertiary color complementary to the seed.
+    Fidelity,
+}
+
+impl Default for Variant {
+    fn default() -> Self {
+        Variant::TonalSpot
+    }
+}
+
+/// A Material Design 3 color scheme.
+///
+/// Roles are computed as the 2023 Material Color Utilities do: each role has a tone in one of the
+/// palettes, adjusted to meet contrast requirements with the background it is shown on.
+///
+/// <https://m3.material.io/styles/color/roles>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Scheme {
+    /// The seed color.
+    pub source: Srgb,
+    /// The style of the scheme.
+    pub variant: Variant,
+    /// Whether the scheme is dark.
+    pub dark: bool,
+    /// The contrast level, from -1 for reduced contrast through 0 for standard contrast to 1 for
+    /// high contrast.
+    pub contrast_level: f32,
+
+    /// The palette of the primary roles.
+    pub primary_palette: TonalPalette,
+    /// The palette of the secondary roles.
+    pub secondary_palette: TonalPalette,
+    /// The palette of the tertiary roles.
+    pub tertiary_palette: TonalPalette,
+    /// The palette of the surface roles.
+    pub neutral_palette: TonalPalette,
+    /// The palette of the surface variant and outline roles.
+    pub neutral_variant_palette: TonalPalette,
+    /// The palette of the error roles.
+    pub error_palette: TonalPalette,
+
+    /// `background`.
+    pub background: Srgb,
+    /// `onBackground`.
+    pub on_background: Srgb,
+    /// `surface`.
+    pub surface: Srgb,
+    /// `surfaceDim`.
+    pub surface_dim: Srgb,
+    /// `surfaceBright`.
+    pub surface_bright: Srgb,
+    /// `surfaceContainerLowest`.
+    pub surface_container_lowest: Srgb,
+    /// `surfaceContainerLow`.
+    pub surface_container_low: Srgb,
+    /// `surfaceContainer`.
+    pub surface_container: Srgb,
+    /// `surfaceContainerHigh`.
+    pub surface_container_high: Srgb,
+    /// `surfaceContainerHighest`.
+    pub surface_container_highest: Srgb,
+    /// `onSurface`.
+    pub on_surface: Srgb,
+    /// `surfaceVariant`.
+    pub surface_variant: Srgb,
+    /// `onSurfaceVariant`.
+    pub on_surface_variant: Srgb,
+    /// `inverseSurface`.
+    pub inverse_surface: Srgb,
+    /// `inverseOnSurface`.
+    pub inverse_on_surface: Srgb,
+    /// `outline`.
+    pub outline: Srgb,
+    /// `outlineVariant`.
+    pub outline_variant: Srgb,
+    /// `shadow`.
+    pub shadow: Srgb,
+    /// `scrim`.
+    pub scrim: Srgb,
+    /// `surfaceTint`.
+    pub surface_tint: Srgb,
+
+    /// `primary`.
+    pub primary: Srgb,
+    /// `onPrimary`.
+    pub on_primary: Srgb,
+    /// `primaryContainer`.
+    pub primary_container: Srgb,
+    /// `onPrimaryContainer`.
+    pub on_primary_container: Srgb,
+    /// `inversePrimary`.
+    pub inverse_primary: Srgb,
+    /// `secondary`.
+    pub secondary: Srgb,
+    /// `onSecondary`.
+    pub on_secondary: Srgb,
+    /// `secondaryContainer`.
+    pub secondary_container: Srgb,
+    /// `onSecondaryContainer`.
+    pub on_secondary_container: Srgb,
+    /// `tertiary`.
+    pub tertiary: Srgb,
+    /// `onTertiary`.
+    pub on_tertiary: Srgb,
+    /// `tertiaryContainer`.
+    pub tertiary_container: Srgb,
+    /// `onTertiaryContainer`.
+    pub on_tertiary_container: Srgb,
+    /// `error`.
+    pub error: Srgb,
+    /// `onError`.
+    pub on_error: Srgb,
+    /// `errorContainer`.
+    pub error_container: Srgb,
+    /// `onErrorContainer`.
+    pub on_error_container: Srgb,
+
+    /// `primaryFixed`.
+    pub primary_fixed: Srgb,
+    /// `primaryFixedDim`.
+    pub primary_fixed_dim: Srgb,
+    /// `onPrimaryFixed`.
+    pub on_primary_fixed: Srgb,
+    /// `onPrimaryFixedVariant`.
+    pub on_primary_fixed_variant: Srgb,
+    /// `secondaryFixed`.
+    pub secondary_fixed: Srgb,
+    /// `secondaryFixedDim`.
+    pub secondary_fixed_dim: Srgb,
+    /// `onSecondaryFixed`.
+    pub on_secondary_fixed: Srgb,
+    /// `onSecondaryFixedVariant`.
+    pub on_secondary_fixed_variant: Srgb,
+    /// `tertiaryFixed`.
+    pub tertiary_fixed: Srgb,
+    /// `tertiaryFixedDim`.
+    pub tertiary_fixed_dim: Srgb,
+    /// `onTertiaryFixed`.
+    pub on_tertiary_fixed: Srgb,
+    /// `onTertiaryFixedVariant`.
+    pub on_tertiary_fixed_variant: Srgb,
+}
+
+impl Scheme {
+    /// Generates a scheme from a seed color, which is rounded to 8 bits and made opaque.
+    pub fn new(source: Srgb, variant: Variant, dark: bool, contrast_level: f32) -> Scheme {
+        let seed = Color::from_srgb(source);
+        let (hue, chroma) = (seed.hue, seed.chroma);
+        let palette = |hue: f64, chroma: f64| TonalPalette::new(hue as f32, chroma as f32);
+        let [primary, secondary, tertiary, neutral, neutral_variant] = match variant {
+            Variant::TonalSpot => [
+                palette(hue, 36.),
+                palette(hue, 16.),
+                palette(sanitize_degrees(hue + 60.), 24.),
+                palette(hue, 6.),
+                palette(hue, 8.),
+            ],
+            Variant::Vibrant => [
+                palette(hue, 200.),
+                palette(
+                    rotated_hue(hue, &VIBRANT_HUES, &VIBRANT_SECONDARY_ROTATIONS),
+                    24.,
+                ),
+                palette(
+                    rotated_hue(hue, &VIBRANT_HUES, &VIBRANT_TERTIARY_ROTATIONS),
+                    32.,
+                ),
+                palette(hue, 10.),
+                palette(hue, 12.),
+            ],
+            Variant::Expressive => [
+                palette(sanitize_degrees(hue + 240.), 40.),
+                palette(
+                    rotated_hue(hue, &EXPRESSIVE_HUES, &EXPRESSIVE_SECONDARY_ROTATIONS),
+                    24.,
+                ),
+                palette(
+                    rotated_hue(hue, &EXPRESSIVE_HUES, &EXPRESSIVE_TERTIARY_ROTATIONS),
+                    32.,
+                ),
+                palette(sanitize_degrees(hue + 15.), 8.),
+                palette(sanitize_degrees(hue + 15.), 12.),
+            ],
+            Variant::Content | Variant::Fidelity => {
+                let temperatures = TemperatureCache::new(seed);
+                let tertiary = if variant == Variant::Content {
+                    temperatures.analogous(3, 6)[2]
+                } else {
+                    temperatures.complement()
+                };
+                [
+                    palette(hue, chroma),
+                    palette(hue, (chroma - 32.).max(chroma * 0.5)),
+                    TonalPalette::from_color(fix_if_disliked(tertiary)),
+                    palette(hue, chroma / 8.),
+                    palette(hue, chroma / 8. + 4.),
+                ]
+            }
+        };
+        let scheme = DynamicScheme {
+            source: seed,
+            variant,
+            dark,
+            contrast_level: f64::from(contrast_level),
+            palettes: [
+                primary,
+                secondary,
+                tertiary,
+                TonalPalette::new(25., 84.),
+                neutral,
+                neutral_variant,
+            ],
+        };
+        let color = |role: Role| scheme.color(role);
+        Scheme {
+            source: Srgb::from_rgb8(seed.rgb[0], seed.rgb[1], seed.rgb[2]),
+            variant,
+            dark,
+            contrast_level,
+
+            primary_palette: primary,
+            secondary_palette: secondary,
+            tertiary_palette: tertiary,
+            neutral_palette: neutral,
+            neutral_variant_palette: neutral_variant,
+            error_palette: scheme.palettes[3],
+
+            background: color(Role::Background),
+            on_background: color(Role::OnBackground),
+            surface: color(Role::Surface),
+            surface_dim: color(Role::SurfaceDim),
+            surface_bright: color(Role::SurfaceBright),
+            surface_container_lowest: color(Role::SurfaceContainerLowest),
+            surface_container_low: color(Role::SurfaceContainerLow),
+            surface_container: color(Role::SurfaceContainer),
+            surface_container_high: color(Role::SurfaceContainerHigh),
+            surface_container_highest: color(Role::SurfaceContainerHighest),
+            on_surface: color(Role::OnSurface),
+            surface_variant: color(Role::SurfaceVariant),
+            on_surface_variant: color(Role::OnSurfaceVariant),
+            inverse_surface: color(Role::InverseSurface),
+            inverse_on_surface: color(Role::InverseOnSurface),
+            outline: color(Role::Outline),
+            outline_variant: color(Role::OutlineVariant),
+            shadow: color(Role::Shadow),
+            scrim: color(Role::Scrim),
+            surface_tint: color(Role::SurfaceTint),
+
+            primary: color(Role::Accent(Accent::Primary)),
+            on_primary: color(Role::OnAccent(Accent::Primary)),
+            primary_container: color(Role::Container(Accent::Primary)),
+            on_primary_container: color(Role::OnContainer(Accent::Primary)),
+            inverse_primary: color(Role::InversePrimary),
+            secondary: color(Role::Accent(Accent::Secondary)),
+            on_secondary: color(Role::OnAccent(Accent::Secondary)),
+            secondary_container: color(Role::Container(Accent::Secondary)),
+            on_secondary_container: color(Role::OnContainer(Accent::Secondary)),
+            tertiary: color(Role::Accent(Accent::Tertiary)),
+            on_tertiary: color(Role::OnAccent(Accent::Tertiary)),
+            tertiary_container: color(Role::Container(Accent::Tertiary)),
+            on_tertiary_container: color(Role::OnContainer(Accent::Tertiary)),
+            error: color(Role::Accent(Accent::Error)),
+            on_error: color(Role::OnAccent(Accent::Error)),
+            error_container: color(Role::Container(Accent::Error)),
+            on_error_container: color(Role::OnContainer(Accent::Error)),
+
+            primary_fixed: color(Role::Fixed(Accent::Primary)),
+            primary_fixed_dim: color(Role::FixedDim(Accent::Primary)),
+            on_primary_fixed: color(Role::OnFixed(Accent::Primary)),
+            on_primary_fixed_variant: color(Role::OnFixedVariant(Accent::Primary)),
+            secondary_fixed: color(Role::Fixed(Accent::Secondary)),
+            secondary_fixed_dim: color(Role::FixedDim(Accent::Secondary)),
+            on_secondary_fixed: color(Role::OnFixed(Accent::Secondary)),
+            on_secondary_fixed_variant: color(Role::OnFixedVariant(Accent::Secondary)),
+            tertiary_fixed: color(Role::Fixed(Accent::Tertiary)),
+            tertiary_fixed_dim: color(Role::FixedDim(Accent::Tertiary)),
+            on_tertiary_fixed: color(Role::OnFixed(Accent::Tertiary)),
+            on_tertiary_fixed_variant: color(Role::OnFixedVariant(Accent::Tertiary)),
+        }
+    }
+
+    /// Generates a [`Variant::TonalSpot`] scheme with standard contrast.
+    pub fn tonal_spot(source: Srgb, dark: bool) -> Scheme {
+        Scheme::new(source, Variant::TonalSpot, dark, 0.)
+    }
+
+    /// Generates a [`Variant::Vibrant`] scheme with standard contrast.
+    pub fn vibrant(source: Srgb, dark: bool) -> Scheme {
+        Scheme::new(source, Variant::Vibrant, dark, 0.)
+    }
+
+    /// Generates a [`Variant::Expressive`] scheme with standard contrast.
+    pub fn expressive(source: Srgb, dark: bool) -> Scheme {
+        Scheme::new(source, Variant::Expressive, dark, 0.)
+    }
+
+    /// Generates a [`Variant::Content`] scheme with standard contrast.
+    pub fn content(source: Srgb, dark: bool) -> Scheme {
+        Scheme::new(source, Variant::Content, dark, 0.)
+    }
+
+    /// Generates a [`Variant::Fidelity`] scheme with standard contrast.
+    pub fn fidelity(source: Srgb, dark: bool) -> Scheme {
+        Scheme::new(source, Variant::Fidelity, dark, 0.)
+    }
+}
+
+const VIBRANT_HUES: [f64; 9] = [0., 41., 61., 101., 131., 181., 251., 301., 360.];
+const VIBRANT_SECONDARY_ROTATIONS: [f64; 9] = [18., 15., 10., 12., 15., 18., 15., 12., 12.];
+const VIBRANT_TERTIARY_ROTATIONS: [f64; 9] = [35., 30., 20., 25., 30., 35., 30., 25., 25.];
+const EXPRESSIVE_HUES: [f64; 9] = [0., 21., 51., 121., 151., 191., 271., 321., 360.];
+const EXPRESSIVE_SECONDARY_ROTATIONS: [f64; 9] = [45., 95., 45., 20., 45., 90., 45., 45., 45.];
+const EXPRESSIVE_TERTIARY_ROTATIONS: [f64; 9] = [120., 120., 20., 45., 20., 15., 20., 120., 120.];
+
+/// Rotates a hue by the rotation of the range of `hues` it falls in.
+fn rotated_hue(hue: f64, hues: &[f64; 9], rotations: &[f64; 9]) -> f64 {
+    for i in 0..hues.len() - 1 {
+        if hues[i] < hue && hue < hues[i + 1] {
+            return sanitize_degrees(hue + rotations[i]);
+        }
+    }
+    hue
+}
+
+fn sanitize_degrees(degrees: f64) -> f64 {
+    degrees.rem_euclid(360.)
+}
+
+/// An 8-bit sRGB color with its HCT components.
+#[derive(Clone, Copy, Debug)]
+struct Color {
+    rgb: [u8; 3],
+    hue: f64,
+    chroma: f64,
+    tone: f64,
+}
+
+impl Color {
+    fn from_rgb8(rgb: [u8; 3]) -> Color {
+        let [hue, chroma, tone] = hct_from_rgb8(rgb);
+        Color {
+            rgb,
+            hue,
+            chroma,
+            tone,
+        }
+    }
+
+    fn from_srgb(srgb: Srgb) -> Color {
+        let [red, green, blue, _] = to_rgba8(srgb);
+        Color::from_rgb8([red, green, blue])
+    }
+
+    fn from_hct(hue: f64, chroma: f64, tone: f64) -> Color {
+        Color::from_rgb8(rgb8_from_hct(hue, chroma, tone))
+    }
+}
+
+/// Returns whether a color is one of the dark yellow-greens that are universally disliked.
+///
+/// <https://doi.org/10.1016/j.jenvp.2017.07.003>
+fn is_disliked(color: Color) -> bool {
+    let hue = color.hue.round();
+    (90. ..=111.).contains(&hue) && color.chroma.round() > 16. && color.tone.round() < 65.
+}
+
+/// Lightens a disliked color to a tone of 70, which makes it likable.
+fn fix_if_disliked(color: Color) -> Color {
+    if is_disliked(color) {
+        Color::from_hct(color.hue, color.chroma, 70.)
+    } else {
+        color
+    }
+}
+
+/// Finds analogous and complementary colors by their warmth.
+///
+/// <https://github.com/material-foundation/material-color-utilities/blob/main/java/temperature/TemperatureCache.java>
+struct TemperatureCache {
+    input: Color,
+    /// The input's chroma and tone at each whole hue from 0 to 360.
+    hcts_by_hue: Vec<Color>,
+    /// The temperatures of `hcts_by_hue`, followed by that of the input.
+    temperatures: Vec<f64>,
+    coldest: usize,
+    warmest: usize,
+}
+
+impl TemperatureCache {
+    fn new(input: Color) -> TemperatureCache {
+        let hcts_by_hue: Vec<Color> = (0..=360)
+            .map(|hue| Color::from_hct(f64::from(hue), input.chroma, input.tone))
+            .collect();
+        let temperatures: Vec<f64> = hcts_by_hue
+            .iter()
+            .chain(Some(&input))
+            .map(|color| raw_temperature(*color))
+            .collect();
+        let mut by_temperature: Vec<usize> = (0..temperatures.len()).collect();
+        by_temperature.sort_by(|a, b| {
+            temperatures[*a]
+                .partial_cmp(&temperatures[*b])
+                .unwrap_or(Ordering::Equal)
+        });
+        TemperatureCache {
+            input,
+            hcts_by_hue,
+            coldest: by_temperature[0],
+            warmest: by_temperature[by_temperature.len() - 1],
+            temperatures,
+        }
+    }
+
+    fn color(&self, index: usize) -> Color {
+        self.hcts_by_hue.get(index).copied().unwrap_or(self.input)
+    }
+
+    /// Returns the temperature relative to the coldest and warmest colors, from 0 to 1.
+    fn relative_temperature(&self, index: usize) -> f64 {
+        let coldest = self.temperatures[self.coldest];
+        let range = self.temperatures[self.warmest] - coldest;
+        if range == 0. {
+            return 0.5;
+        }
+        (self.temperatures[index] - coldest) / range
+    }
+
+    /// Returns the color on the opposite side of the warmth range, with the inverse relative
+    /// temperature.
+    fn complement(&self) -> Color {
+        let coldest_hue = self.color(self.coldest).hue;
+        let warmest_hue = self.color(self.warmest).hue;
+        let (start_hue, end_hue) = if is_between(self.input.hue, coldest_hue, warmest_hue) {
+            (warmest_hue, coldest_hue)
+        } else {
+            (coldest_hue, warmest_hue)
+        };
+        let complement_temperature = 1. - self.relative_temperature(self.hcts_by_hue.len());
+        let mut smallest_error = 1000.;
+        let mut answer = self.hcts_by_hue[self.input.hue.round() as usize];
+        for hue_addend in 0..=360 {
+            let hue = sanitize_degrees(start_hue + f64::from(hue_addend));
+            if !is_between(hue, start_hue, end_hue) {
+                continue;
+            }
+            let index = hue.round() as usize;
+            let error = (complement_temperature - self.relative_temperature(index)).abs();
+            if error < smallest_error {
+                smallest_error = error;
+                answer = self.hcts_by_hue[index];
+            }
+        }
+        answer
+    }
+
+    /// Returns `count` colors around the input, which is in the middle, chosen from `divisions`
+    /// colors evenly spaced in temperature around the hue circle.
+    fn analogous(&self, count: usize, divisions: usize) -> Vec<Color> {
+        let start_hue = self.input.hue.round() as usize;
+        let hue_at = |addend: usize| (start_hue + addend) % 360;
+
+        let mut absolute_total_delta = 0.;
+        let mut last = self.relative_temperature(start_hue);
+        for i in 0..360 {
+            let temperature = self.relative_temperature(hue_at(i));
+            absolute_total_delta += (temperature - last).abs();
+            last = temperature;
+        }
+
+        let step = absolute_total_delta / divisions as f64;
+        let mut all_colors = vec![self.hcts_by_hue[start_hue]];
+        let mut total_delta = 0.;
+        let mut last = self.relative_temperature(start_hue);
+        let mut hue_addend = 1;
+        while all_colors.len() < divisions {
+            let hue = hue_at(hue_addend);
+            let temperature = self.relative_temperature(hue);
+            total_delta += (temperature - last).abs();
+            // Adds the hue as many times as the temperature steps it covers, so that colors
+            // without analogues, such as white, still fill every division.
+            let mut index_addend = 0;
+            while all_colors.len() < divisions
+                && total_delta >= (all_colors.len() + index_addend) as f64 * step
+            {
+                all_colors.push(self.hcts_by_hue[hue]);
+                index_addend += 1;
+            }
+            last = temperature;
+            hue_addend += 1;
+            if hue_addend > 360 {
+                all_colors.resize(divisions, self.hcts_by_hue[hue]);
+            }
+        }
+
+        let ccw_count = (count - 1) / 2;
+        let mut answers: Vec<Color> = (1..=ccw_count)
+            .rev()
+            .map(|i| all_colors[(all_colors.len() - i % all_colors.len()) % all_colors.len()])
+            .collect();
+        answers.push(self.input);
+        answers.extend((1..count - ccw_count).map(|i| all_colors[i % all_colors.len()]));
+        answers
+    }
+}
+
+/// Returns whether `angle` is on the arc from `a` to `b`, clockwise.
+fn is_between(angle: f64, a: f64, b: f64) -> bool {
+    if a < b {
+        a <= angle && angle <= b
+    } else {
+        a <= angle || angle <= b
+    }
+}
+
+/// Returns the warmth of a color, from about -0.5 for cold blues to 1.5 for warm oranges.
+///
+/// <https://doi.org/10.1002/col.20010>
+fn raw_temperature(color: Color) -> f64 {
+    let [_, a, b] = lab_from_rgb8(color.rgb);
+    let hue = sanitize_degrees(b.atan2(a).to_degrees());
+    let chroma = a.hypot(b);
+    -0.5 + 0.02 * chroma.powf(1.07) * sanitize_degrees(hue - 50.).to_radians().cos()
+}
+
+/// A color role whose tone depends on the scheme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Role {
+    Background,
+    OnBackground,
+    Surface,
+    SurfaceDim,
+    SurfaceBright,
+    SurfaceContainerLowest,
+    SurfaceContainerLow,
+    SurfaceContainer,
+    SurfaceContainerHigh,
+    SurfaceContainerHighest,
+    OnSurface,
+    SurfaceVariant,
+    OnSurfaceVariant,
+    InverseSurface,
+    InverseOnSurface,
+    Outline,
+    OutlineVariant,
+    Shadow,
+    Scrim,
+    SurfaceTint,
+    InversePrimary,
+    Accent(Accent),
+    OnAccent(Accent),
+    Container(Accent),
+    OnContainer(Accent),
+    Fixed(Accent),
+    FixedDim(Accent),
+    OnFixed(Accent),
+    OnFixedVariant(Accent),
+}
+
+/// The accent palettes, in the order of `DynamicScheme::palettes`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Accent {
+    Primary,
+    Secondary,
+    Tertiary,
+    Error,
+}
+
+const NEUTRAL: usize = 4;
+const NEUTRAL_VARIANT: usize = 5;
+
+// Minimum contrast ratios at contrast levels -1, 0, 0.5 and 1.
+const ON_COLOR_CONTRAST: [f64; 4] = [4.5, 7., 11., 21.];
+const ON_VARIANT_CONTRAST: [f64; 4] = [3., 4.5, 7., 11.];
+const ACCENT_CONTRAST: [f64; 4] = [3., 4.5, 7., 7.];
+const CONTAINER_CONTRAST: [f64; 4] = [1., 1., 3., 4.5];
+const ON_BACKGROUND_CONTRAST: [f64; 4] = [3., 3., 4.5, 7.];
+const OUTLINE_CONTRAST: [f64; 4] = [1.5, 3., 4.5, 7.];
+
+/// Which role of a [`ToneDeltaPair`] is nearer to the background.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Polarity {
+    /// The first role.
+    Nearer,
+    /// The first role in light schemes, the second in dark schemes.
+    Lighter,
+}
+
+/// Two roles whose tones stay at least `delta` apart.
+#[derive(Clone, Copy, Debug)]
+struct ToneDeltaPair {
+    a: Role,
+    b: Role,
+    delta: f64,
+    polarity: Polarity,
+    stay_together: bool,
+}
+
+/// How the tone of a role is chosen.
+struct RoleSpec {
+    palette: usize,
+    tone: f64,
+    is_background: bool,
+    background: Option<Role>,
+    second_background: Option<Role>,
+    contrast: [f64; 4],
+    pair: Option<ToneDeltaPair>,
+}
+
+struct DynamicScheme {
+    source: Color,
+    variant: Variant,
+    dark: bool,
+    contrast_level: f64,
+    /// The primary, secondary, tertiary, error, neutral and neutral variant palettes.
+    palettes: [TonalPalette; 6],
+}
+
+impl DynamicScheme {
+    fn color(&self, role: Role) -> Srgb {
+        let [red, green, blue] = self.palettes[self.spec(role).palette]
+            .color(self.tone(role))
+            .rgb;
+        Srgb::from_rgb8(red, green, blue)
+    }
+
+    fn is_fidelity(&self) -> bool {
+        matches!(self.variant, Variant::Content | Variant::Fidelity)
+    }
+
+    fn light_dark(&self, light: f64, dark: f64) -> f64 {
+        if self.dark {
+            dark
+        } else {
+            light
+        }
+    }
+
+    fn highest_surface(&self) -> Role {
+        if self.dark {
+            Role::SurfaceBright
+        } else {
+            Role::SurfaceDim
+        }
+    }
+
+    fn spec(&self, role: Role) -> RoleSpec {
+        let surface = |light, dark| RoleSpec {
+            palette: NEUTRAL,
+            tone: self.light_dark(light, dark),
+            is_background: true,
+            background: None,
+            second_background: None,
+            contrast: [1.; 4],
+            pair: None,
+        };
+        let foreground = |palette, light, dark, background, contrast| RoleSpec {
+            palette,
+            tone: self.light_dark(light, dark),
+            is_background: false,
+            background: Some(background),
+            second_background: None,
+            contrast,
+            pair: None,
+        };
+        let highest = self.highest_surface();
+        match role {
+            Role::Background | Role::Surface => surface(98., 6.),
+            Role::SurfaceDim => surface(87., 6.),
+            Role::SurfaceBright => surface(98., 24.),
+            Role::SurfaceContainerLowest => surface(100., 4.),
+            Role::SurfaceContainerLow => surface(96., 10.),
+            Role::SurfaceContainer => surface(94., 12.),
+            Role::SurfaceContainerHigh => surface(92., 17.),
+            Role::SurfaceContainerHighest => surface(90., 22.),
+            Role::SurfaceVariant => RoleSpec {
+                palette: NEUTRAL_VARIANT,
+                ..surface(90., 30.)
+            },
+            Role::InverseSurface => RoleSpec {
+                is_background: false,
+                ..surface(20., 90.)
+            },
+            Role::Shadow | Role::Scrim => RoleSpec {
+                is_background: false,
+                ..surface(0., 0.)
+            },
+            Role::SurfaceTint => RoleSpec {
+                palette: Accent::Primary as usize,
+                ..surface(40., 80.)
+            },
+            Role::OnBackground => {
+                foreground(NEUTRAL, 10., 90., Role::Background, ON_BACKGROUND_CONTRAST)
+            }
+            Role::OnSurface => foreground(NEUTRAL, 10., 90., highest, ON_COLOR_CONTRAST),
+            Role::OnSurfaceVariant => {
+                foreground(NEUTRAL_VARIANT, 30., 80., highest, ON_VARIANT_CONTRAST)
+            }
+            Role::InverseOnSurface => {
+                foreground(NEUTRAL, 95., 20., Role::InverseSurface, ON_COLOR_CONTRAST)
+            }
+            Role::Outline => foreground(NEUTRAL_VARIANT, 50., 60., highest, OUTLINE_CONTRAST),
+            Role::OutlineVariant => {
+                foreground(NEUTRAL_VARIANT, 80., 30., highest, CONTAINER_CONTRAST)
+            }
+            Role::InversePrimary => foreground(
+                Accent::Primary as usize,
+                80.,
+                40.,
+                Role::InverseSurface,
+                ACCENT_CONTRAST,
+            ),
+            Role::Accent(accent) | Role::Container(accent) => {
+                let container = role == Role::Container(accent);
+                let tone = if container {
+                    self.container_tone(accent)
+                } else {
+                    self.light_dark(40., 80.)
+                };
+                RoleSpec {
+                    palette: accent as usize,
+                    tone,
+                    is_background: true,
+                    background: Some(highest),
+                    second_background: None,
+                    contrast: if container {
+                        CONTAINER_CONTRAST
+                    } else {
+                        ACCENT_CONTRAST
+                    },
+                    pair: Some(ToneDeltaPair {
+                        a: Role::Container(accent),
+                        b: Role::Accent(accent),
+                        delta: 10.,
+                        polarity: Polarity::Nearer,
+                        stay_together: false,
+                    }),
+                }
+            }
+            Role::OnAccent(accent) => foreground(
+                accent as usize,
+                100.,
+                20.,
+                Role::Accent(accent),
+                ON_COLOR_CONTRAST,
+            ),
+            Role::OnContainer(accent) => {
+                let mut spec = foreground(
+                    accent as usize,
+                    10.,
+                    90.,
+                    Role::Container(accent),
+                    ON_COLOR_CONTRAST,
+                );
+                if self.is_fidelity() && accent != Accent::Error {
+                    spec.tone = foreground_tone(self.container_tone(accent), 4.5);
+                }
+                spec
+            }
+            Role::Fixed(accent) | Role::FixedDim(accent) => RoleSpec {
+                palette: accent as usize,
+                tone: if role == Role::Fixed(accent) {
+                    90.
+                } else {
+                    80.
+                },
+                is_background: true,
+                background: Some(highest),
+                second_background: None,
+                contrast: CONTAINER_CONTRAST,
+                pair: Some(ToneDeltaPair {
+                    a: Role::Fixed(accent),
+                    b: Role::FixedDim(accent),
+                    delta: 10.,
+                    polarity: Polarity::Lighter,
+                    stay_together: true,
+                }),
+            },
+            Role::OnFixed(accent) | Role::OnFixedVariant(accent) => {
+                let variant = role == Role::OnFixedVariant(accent);
+                RoleSpec {
+                    palette: accent as usize,
+                    tone: if variant { 30. } else { 10. },
+                    is_background: false,
+                    background: Some(Role::FixedDim(accent)),
+                    second_background: Some(Role::Fixed(accent)),
+                    contrast: if variant {
+                        ON_VARIANT_CONTRAST
+                    } else {
+                        ON_COLOR_CONTRAST
+                    },
+                    pair: None,
+                }
+            }
+        }
+    }
+
+    /// Returns the tone of a container role before contrast adjustments.
+    fn container_tone(&self, accent: Accent) -> f64 {
+        let tone = self.light_dark(90., 30.);
+        if !self.is_fidelity() {
+            return tone;
+        }
+        let palette = self.palettes[accent as usize];
+        match accent {
+            Accent::Primary => self.source.tone,
+            Accent::Secondary => {
+                desired_chroma_tone(palette.hue.into(), palette.chroma.into(), tone, !self.dark)
+            }
+            Accent::Tertiary => fix_if_disliked(palette.color(self.source.tone)).tone,
+            Accent::Error => tone,
+        }
+    }
+
+    fn contrast_ratio(&self, curve: [f64; 4]) -> f64 {
+        let [low, normal, medium, high] = curve;
+        let level = self.contrast_level;
+        if level <= -1. {
+            low
+        } else if level < 0. {
+            low + (normal - low) * (level + 1.)
+        } else if level < 0.5 {
+            normal + (medium - normal) * level / 0.5
+        } else if level < 1. {
+            medium + (high - medium) * (level - 0.5) / 0.5
+        } else {
+            high
+        }
+    }
+
+    /// Returns the tone of a role after contrast adjustments.
+    ///
+    /// <https://github.com/material-foundation/material-color-utilities/blob/main/java/dynamiccolor/DynamicColor.java>
+    fn tone(&self, role: Role) -> f64 {
+        let spec = self.spec(role);
+        let decreasing_contrast = self.contrast_level < 0.;
+        let background = match spec.background {
+            Some(background) => background,
+            None => return spec.tone,
+        };
+        let bg_tone = self.tone(background);
+
+        if let Some(pair) = spec.pair {
+            let a_is_nearer = match pair.polarity {
+                Polarity::Nearer => true,
+                Polarity::Lighter => !self.dark,
+            };
+            let (nearer, farther) = if a_is_nearer {
+                (pair.a, pair.b)
+            } else {
+                (pair.b, pair.a)
+            };
+            let (nearer, farther) = (self.spec(nearer), self.spec(farther));
+            let am_nearer = role == if a_is_nearer { pair.a } else { pair.b };
+            let expansion = if self.dark { 1. } else { -1. };
+            let delta = pair.delta;
+
+            let n_contrast = self.contrast_ratio(nearer.contrast);
+            let f_contrast = self.contrast_ratio(farther.contrast);
+            let initial_or_foreground = |tone: f64, contrast: f64| {
+                if ratio_of_tones(bg_tone, tone) >= contrast && !decreasing_contrast {
+                    tone
+                } else {
+                    foreground_tone(bg_tone, contrast)
+                }
+            };
+            let mut n_tone = initial_or_foreground(nearer.tone, n_contrast);
+            let mut f_tone = initial_or_foreground(farther.tone, f_contrast);
+
+            if (f_tone - n_tone) * expansion < delta {
+                f_tone = (n_tone + delta * expansion).clamp(0., 100.);
+                if (f_tone - n_tone) * expansion < delta {
+                    n_tone = (f_tone - delta * expansion).clamp(0., 100.);
+                }
+            }
+
+            // Avoids the awkward zone of tones 50 to 59, moving both roles if the nearer one is
+            // in it or if they must stay on the same side.
+            let in_awkward_zone = |tone: f64| (50. ..60.).contains(&tone);
+            if in_awkward_zone(n_tone) || (in_awkward_zone(f_tone) && pair.stay_together) {
+                if expansion > 0. {
+                    n_tone = 60.;
+                    f_tone = f_tone.max(n_tone + delta * expansion);
+                } else {
+                    n_tone = 49.;
+                    f_tone = f_tone.min(n_tone + delta * expansion);
+                }
+            } else if in_awkward_zone(f_tone) {
+                f_tone = if expansion > 0. { 60. } else { 49. };
+            }
+
+            return if am_nearer { n_tone } else { f_tone };
+        }
+
+        let desired_ratio = self.contrast_ratio(spec.contrast);
+        let mut answer = spec.tone;
+        if ratio_of_tones(bg_tone, answer) < desired_ratio || decreasing_contrast {
+            answer = foreground_tone(bg_tone, desired_ratio);
+        }
+        if spec.is_background && (50. ..60.).contains(&answer) {
+            answer = if ratio_of_tones(49., bg_tone) >= desired_ratio {
+                49.
+            } else {
+                60.
+            };
+        }
+
+        if let Some(second_background) = spec.second_background {
+            let bg_tone2 = self.tone(second_background);
+            let upper = bg_tone.max(bg_tone2);
+            let lower = bg_tone.min(bg_tone2);
+            if ratio_of_tones(upper, answer) >= desired_ratio
+                && ratio_of_tones(lower, answer) >= desired_ratio
+            {
+                return answer;
+            }
+            let light_option = lighter_tone(upper, desired_ratio);
+            let dark_option = darker_tone(lower, desired_ratio);
+            if prefers_light_foreground(bg_tone) || prefers_light_foreground(bg_tone2) {
+                return light_option.unwrap_or(100.);
+            }
+            return match (light_option, dark_option) {
+                (Some(light), None) => light,
+                (_, dark) => dark.unwrap_or(0.),
+            };
+        }
+        answer
+    }
+}
+
+/// Returns the tone with the requested chroma nearest to `tone`, or with the chroma closest to it
+/// if it cannot be reached, searching towards darker or lighter tones.
+fn desired_chroma_tone(hue: f64, chroma: f64, tone: f64, by_decreasing_tone: bool) -> f64 {
+    let mut answer = tone;
+    let mut closest = Color::from_hct(hue, chroma, tone);
+    if closest.chroma < chroma {
+        let mut chroma_peak = closest.chroma;
+        while closest.chroma < chroma {
+            answer += if by_decreasing_tone { -1. } else { 1. };
+            let potential = Color::from_hct(hue, chroma, answer);
+            if chroma_peak > potential.chroma || (potential.chroma - chroma).abs() < 0.4 {
+                break;
+            }
+            if (potential.chroma - chroma).abs() < (closest.chroma - chroma).abs() {
+                closest = potential;
+            }
+            chroma_peak = chroma_peak.max(potential.chroma);
+        }
+    }
+    answer
+}
+
+/// Returns whether a background tone looks better with light text, ignoring contrast.
+fn prefers_light_foreground(tone: f64) -> bool {
+    tone.round() < 60.
+}
+
+/// Returns the tone with the given contrast ratio against a background tone, preferring lighter
+/// tones on dark backgrounds and darker tones on light backgrounds.
+fn foreground_tone(bg_tone: f64, ratio: f64) -> f64 {
+    let lighter = lighter_tone(bg_tone, ratio).unwrap_or(100.);
+    let darker = darker_tone(bg_tone, ratio).unwrap_or(0.);
+    let lighter_ratio = ratio_of_tones(lighter, bg_tone);
+    let darker_ratio = ratio_of_tones(darker, bg_tone);
+    if prefers_light_foreground(bg_tone) {
+        let negligible_difference = (lighter_ratio - darker_ratio).abs() < 0.1
+            && lighter_ratio < ratio
+            && darker_ratio < ratio;
+        if lighter_ratio >= ratio || lighter_ratio >= darker_ratio || negligible_difference {
+            lighter
+        } else {
+            darker
+        }
+    } else if darker_ratio >= ratio || darker_ratio >= lighter_ratio {
+        darker
+    } else {
+        lighter
+    }
+}
+
+/// Returns the WCAG contrast ratio of two tones.
+fn ratio_of_tones(a: f64, b: f64) -> f64 {
+    let (a, b) = (
+        y_from_lstar(a.clamp(0., 100.)),
+        y_from_lstar(b.clamp(0., 100.)),
+    );
+    (a.max(b) + 5.) / (a.min(b) + 5.)
+}
+
+// Tones are moved 0.4 further than needed, since rounding to 8 bits can reduce the contrast.
+const LUMINANCE_GAMUT_MAP_TOLERANCE: f64 = 0.4;
+const CONTRAST_RATIO_EPSILON: f64 = 0.04;
+
+/// Returns the lightest tone with at least the given contrast ratio against a tone, if any.
+fn lighter_tone(tone: f64, ratio: f64) -> Option<f64> {
+    if !(0. ..=100.).contains(&tone) {
+        return None;
+    }
+    let dark_y = y_from_lstar(tone);
+    let light_y = ratio * (dark_y + 5.) - 5.;
+    contrasting_tone(light_y, dark_y, ratio, LUMINANCE_GAMUT_MAP_TOLERANCE)
+}
+
+/// Returns the darkest tone with at least the given contrast ratio against a tone, if any.
+fn darker_tone(tone: f64, ratio: f64) -> Option<f64> {
+    if !(0. ..=100.).contains(&tone) {
+        return None;
+    }
+    let light_y = y_from_lstar(tone);
+    let dark_y = (light_y + 5.) / ratio - 5.;
+    contrasting_tone(dark_y, light_y, ratio, -LUMINANCE_GAMUT_MAP_TOLERANCE)
+}
+
+fn contrasting_tone(y: f64, other_y: f64, ratio: f64, tolerance: f64) -> Option<f64> {
+    if !(0. ..=100.).contains(&y) {
+        return None;
+    }
+    let real_contrast = (y.max(other_y) + 5.) / (y.min(other_y) + 5.);
+    if real_contrast < ratio && (real_contrast - ratio).abs() > CONTRAST_RATIO_EPSILON {
+        return None;
+    }
+    let tone = lstar_from_y(y) + tolerance;
+    if (0. ..=100.).contains(&tone) {
+        Some(tone)
+    } else {
+        None
+    }
+}
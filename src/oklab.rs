@@ -0,0 +1,159 @@
+use crate::lab::{from_polar, to_polar};
+use crate::space::ColorSpace;
+use crate::xyz::{multiply, Matrix};
+use crate::XyzD65;
+
+/// A color in the Oklab color space.
+///
+/// <https://www.w3.org/TR/css-color-4/#ok-lab>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Oklab {
+    /// The lightness component, from 0 to 1.
+    pub lightness: f32,
+    /// The a component.
+    pub a: f32,
+    /// The b component.
+    pub b: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+/// A color in the Oklch color space, the polar form of [`Oklab`].
+///
+/// <https://www.w3.org/TR/css-color-4/#ok-lab>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Oklch {
+    /// The lightness component, from 0 to 1.
+    pub lightness: f32,
+    /// The chroma component.
+    pub chroma: f32,
+    /// The hue component in degrees.
+    pub hue: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+impl Oklab {
+    pub fn new(lightness: f32, a: f32, b: f32, alpha: f32) -> Oklab {
+        Oklab {
+            lightness,
+            a,
+            b,
+            alpha,
+        }
+    }
+}
+
+impl Oklch {
+    pub fn new(lightness: f32, chroma: f32, hue: f32, alpha: f32) -> Oklch {
+        Oklch {
+            lightness,
+            chroma,
+            hue,
+            alpha,
+        }
+    }
+
+    /// Returns `true` if the hue has no effect on the color.
+    pub fn is_hue_powerless(&self) -> bool {
+        self.chroma <= OKLCH_ACHROMATIC_THRESHOLD
+    }
+}
+
+const OKLCH_ACHROMATIC_THRESHOLD: f32 = 0.000004;
+
+// https://www.w3.org/TR/css-color-4/#color-conversion-code
+#[allow(clippy::excessive_precision)]
+const XYZ_TO_LMS: Matrix = [
+    [0.8190224379967030, 0.3619062600528904, -0.1288737815209879],
+    [0.0329836539323885, 0.9292868615863434, 0.0361446663506424],
+    [0.0481771893596242, 0.2642395317527308, 0.6335478284694309],
+];
+
+#[allow(clippy::excessive_precision)]
+const LMS_TO_OKLAB: Matrix = [
+    [0.2104542683093140, 0.7936177747023054, -0.0040720430116193],
+    [1.9779985324311684, -2.4285922420485799, 0.4505937096174110],
+    [0.0259040424655478, 0.7827717124575296, -0.8086757549230774],
+];
+
+#[allow(clippy::excessive_precision)]
+const LMS_TO_XYZ: Matrix = [
+    [1.2268798758459243, -0.5578149944602171, 0.2813910456659647],
+    [-0.0405757452148008, 1.1122868032803170, -0.0717110580655164],
+    [-0.0763729366746601, -0.4214933324022432, 1.5869240198367816],
+];
+
+#[allow(clippy::excessive_precision)]
+const OKLAB_TO_LMS: Matrix = [
+    [1.0000000000000000, 0.3963377773761749, 0.2158037573099136],
+    [1.0000000000000000, -0.1055613458156586, -0.0638541728258133],
+    [1.0000000000000000, -0.0894841775298119, -1.2914855480194092],
+];
+
+impl From<XyzD65> for Oklab {
+    fn from(xyz: XyzD65) -> Self {
+        let lms = multiply(&XYZ_TO_LMS, [xyz.x, xyz.y, xyz.z]);
+        let lms = lms.map(|value| (value as f64).cbrt() as f32);
+        let [lightness, a, b] = multiply(&LMS_TO_OKLAB, lms);
+        Oklab {
+            lightness,
+            a,
+            b,
+            alpha: xyz.alpha,
+        }
+    }
+}
+
+impl From<Oklab> for XyzD65 {
+    fn from(oklab: Oklab) -> Self {
+        let lms = multiply(&OKLAB_TO_LMS, [oklab.lightness, oklab.a, oklab.b]);
+        let lms = lms.map(|value| (value as f64).powi(3) as f32);
+        let [x, y, z] = multiply(&LMS_TO_XYZ, lms);
+        XyzD65::new(x, y, z, oklab.alpha)
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(oklab: Oklab) -> Self {
+        let (chroma, hue) = to_polar(oklab.a, oklab.b, OKLCH_ACHROMATIC_THRESHOLD);
+        Oklch {
+            lightness: oklab.lightness,
+            chroma,
+            hue,
+            alpha: oklab.alpha,
+        }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(oklch: Oklch) -> Self {
+        let (a, b) = from_polar(oklch.chroma, oklch.hue);
+        Oklab {
+            lightness: oklch.lightness,
+            a,
+            b,
+            alpha: oklch.alpha,
+        }
+    }
+}
+
+impl ColorSpace for Oklab {
+    fn to_xyz(self) -> XyzD65 {
+        XyzD65::from(self)
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        Oklab::from(xyz)
+    }
+}
+
+impl ColorSpace for Oklch {
+    fn to_xyz(self) -> XyzD65 {
+        XyzD65::from(Oklab::from(self))
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        Oklch::from(Oklab::from(xyz))
+    }
+}
@@ -0,0 +1,226 @@
+use crate::linear::{linear_to_srgb, srgb_to_linear};
+use crate::space::ColorSpace;
+use crate::xyz::{multiply, Matrix};
+use crate::{LinearSrgb, Srgb, XyzD50, XyzD65};
+
+/// A color in the Display P3 color space.
+///
+/// <https://www.w3.org/TR/css-color-4/#predefined-display-p3>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DisplayP3 {
+    /// The red component.
+    pub red: f32,
+    /// The green component.
+    pub green: f32,
+    /// The blue component.
+    pub blue: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+/// A color in the ITU-R BT.2020 color space.
+///
+/// <https://www.w3.org/TR/css-color-4/#predefined-rec2020>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rec2020 {
+    /// The red component.
+    pub red: f32,
+    /// The green component.
+    pub green: f32,
+    /// The blue component.
+    pub blue: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+/// A predefined color space of the CSS `color()` function.
+///
+/// <https://www.w3.org/TR/css-color-4/#predefined>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PredefinedSpace {
+    /// `srgb`.
+    Srgb,
+    /// `srgb-linear`.
+    SrgbLinear,
+    /// `display-p3`.
+    DisplayP3,
+    /// `rec2020`.
+    Rec2020,
+    /// `xyz-d50`.
+    XyzD50,
+    /// `xyz-d65`, also written `xyz`.
+    XyzD65,
+}
+
+impl PredefinedSpace {
+    /// Returns the name of the color space in `color()`.
+    pub fn name(self) -> &'static str {
+        match self {
+            PredefinedSpace::Srgb => "srgb",
+            PredefinedSpace::SrgbLinear => "srgb-linear",
+            PredefinedSpace::DisplayP3 => "display-p3",
+            PredefinedSpace::Rec2020 => "rec2020",
+            PredefinedSpace::XyzD50 => "xyz-d50",
+            PredefinedSpace::XyzD65 => "xyz-d65",
+        }
+    }
+
+    /// Creates a color from components in this color space.
+    pub(crate) fn to_srgb(self, [c0, c1, c2]: [f32; 3], alpha: f32) -> Srgb {
+        match self {
+            PredefinedSpace::Srgb => Srgb::new(c0, c1, c2, alpha),
+            PredefinedSpace::SrgbLinear => LinearSrgb::new(c0, c1, c2, alpha).convert(),
+            PredefinedSpace::DisplayP3 => DisplayP3::new(c0, c1, c2, alpha).convert(),
+            PredefinedSpace::Rec2020 => Rec2020::new(c0, c1, c2, alpha).convert(),
+            PredefinedSpace::XyzD50 => XyzD50::new(c0, c1, c2, alpha).convert(),
+            PredefinedSpace::XyzD65 => XyzD65::new(c0, c1, c2, alpha).convert(),
+        }
+    }
+
+    /// Returns the components of a color in this color space.
+    pub(crate) fn components(self, color: Srgb) -> [f32; 3] {
+        match self {
+            PredefinedSpace::Srgb => [color.red, color.green, color.blue],
+            PredefinedSpace::SrgbLinear => {
+                let color = color.convert::<LinearSrgb>();
+                [color.red, color.green, color.blue]
+            }
+            PredefinedSpace::DisplayP3 => {
+                let color = color.convert::<DisplayP3>();
+                [color.red, color.green, color.blue]
+            }
+            PredefinedSpace::Rec2020 => {
+                let color = color.convert::<Rec2020>();
+                [color.red, color.green, color.blue]
+            }
+            PredefinedSpace::XyzD50 => {
+                let color = color.convert::<XyzD50>();
+                [color.x, color.y, color.z]
+            }
+            PredefinedSpace::XyzD65 => {
+                let color = color.convert::<XyzD65>();
+                [color.x, color.y, color.z]
+            }
+        }
+    }
+}
+
+impl DisplayP3 {
+    pub fn new(red: f32, green: f32, blue: f32, alpha: f32) -> DisplayP3 {
+        DisplayP3 {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+}
+
+impl Rec2020 {
+    pub fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Rec2020 {
+        Rec2020 {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+}
+
+// https://www.w3.org/TR/css-color-4/#color-conversion-code
+const LINEAR_P3_TO_XYZ_D65: Matrix = [
+    [608311. / 1250200., 189793. / 714400., 198249. / 1000160.],
+    [35783. / 156275., 247089. / 357200., 198249. / 2500400.],
+    [0., 32229. / 714400., 5220557. / 5000800.],
+];
+
+const XYZ_D65_TO_LINEAR_P3: Matrix = [
+    [446124. / 178915., -333277. / 357830., -72051. / 178915.],
+    [-14852. / 17905., 63121. / 35810., 423. / 17905.],
+    [11844. / 330415., -50337. / 660830., 316169. / 330415.],
+];
+
+const LINEAR_REC2020_TO_XYZ_D65: Matrix = [
+    [
+        63426534. / 99577255.,
+        20160776. / 139408157.,
+        47086771. / 278816314.,
+    ],
+    [
+        26158966. / 99577255.,
+        472592308. / 697040785.,
+        8267143. / 139408157.,
+    ],
+    [0., 19567812. / 697040785., 295819943. / 278816314.],
+];
+
+const XYZ_D65_TO_LINEAR_REC2020: Matrix = [
+    [
+        30757411. / 17917100.,
+        -6372589. / 17917100.,
+        -4539589. / 17917100.,
+    ],
+    [
+        -19765991. / 29648200.,
+        47925759. / 29648200.,
+        467509. / 29648200.,
+    ],
+    [
+        792561. / 44930125.,
+        -1921689. / 44930125.,
+        42328811. / 44930125.,
+    ],
+];
+
+const REC2020_ALPHA: f64 = 1.09929682680944;
+const REC2020_BETA: f64 = 0.018053968510807;
+
+fn rec2020_to_linear(value: f32) -> f32 {
+    let value = value as f64;
+    let abs = value.abs();
+    let linear = if abs < REC2020_BETA * 4.5 {
+        abs / 4.5
+    } else {
+        ((abs + REC2020_ALPHA - 1.) / REC2020_ALPHA).powf(1. / 0.45)
+    };
+    linear.copysign(value) as f32
+}
+
+fn linear_to_rec2020(value: f32) -> f32 {
+    let value = value as f64;
+    let abs = value.abs();
+    let encoded = if abs > REC2020_BETA {
+        REC2020_ALPHA * abs.powf(0.45) - (REC2020_ALPHA - 1.)
+    } else {
+        4.5 * abs
+    };
+    encoded.copysign(value) as f32
+}
+
+impl ColorSpace for DisplayP3 {
+    fn to_xyz(self) -> XyzD65 {
+        let linear = [self.red, self.green, self.blue].map(srgb_to_linear);
+        let [x, y, z] = multiply(&LINEAR_P3_TO_XYZ_D65, linear);
+        XyzD65::new(x, y, z, self.alpha)
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        let linear = multiply(&XYZ_D65_TO_LINEAR_P3, [xyz.x, xyz.y, xyz.z]);
+        let [red, green, blue] = linear.map(linear_to_srgb);
+        DisplayP3::new(red, green, blue, xyz.alpha)
+    }
+}
+
+impl ColorSpace for Rec2020 {
+    fn to_xyz(self) -> XyzD65 {
+        let linear = [self.red, self.green, self.blue].map(rec2020_to_linear);
+        let [x, y, z] = multiply(&LINEAR_REC2020_TO_XYZ_D65, linear);
+        XyzD65::new(x, y, z, self.alpha)
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        let linear = multiply(&XYZ_D65_TO_LINEAR_REC2020, [xyz.x, xyz.y, xyz.z]);
+        let [red, green, blue] = linear.map(linear_to_rec2020);
+        Rec2020::new(red, green, blue, xyz.alpha)
+    }
+}
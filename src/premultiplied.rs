@@ -0,0 +1,129 @@
+use crate::space::ColorSpace;
+use crate::{LinearSrgb, Srgb, XyzD65};
+
+/// A color in the sRGB color space with its color components multiplied by alpha.
+///
+/// Converting to a straight-alpha color divides by alpha, so a fully transparent color converts
+/// to transparent black. Only RGB color spaces have premultiplied forms: the hue of cylindrical
+/// color spaces cannot be multiplied by alpha.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PremultipliedSrgb {
+    /// The red component multiplied by alpha.
+    pub red: f32,
+    /// The green component multiplied by alpha.
+    pub green: f32,
+    /// The blue component multiplied by alpha.
+    pub blue: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+impl PremultipliedSrgb {
+    pub fn new(red: f32, green: f32, blue: f32, alpha: f32) -> PremultipliedSrgb {
+        PremultipliedSrgb {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+}
+
+/// A color in the linear-light sRGB color space with its color components multiplied by alpha.
+///
+/// This is the usual format of blending and texture filtering on GPUs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PremultipliedLinearSrgb {
+    /// The red component multiplied by alpha.
+    pub red: f32,
+    /// The green component multiplied by alpha.
+    pub green: f32,
+    /// The blue component multiplied by alpha.
+    pub blue: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+impl PremultipliedLinearSrgb {
+    pub fn new(red: f32, green: f32, blue: f32, alpha: f32) -> PremultipliedLinearSrgb {
+        PremultipliedLinearSrgb {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+}
+
+fn premultiply([red, green, blue]: [f32; 3], alpha: f32) -> [f32; 3] {
+    [red * alpha, green * alpha, blue * alpha]
+}
+
+fn unpremultiply([red, green, blue]: [f32; 3], alpha: f32) -> [f32; 3] {
+    if alpha == 0. {
+        [0.; 3]
+    } else {
+        [red / alpha, green / alpha, blue / alpha]
+    }
+}
+
+impl From<Srgb> for PremultipliedSrgb {
+    fn from(srgb: Srgb) -> Self {
+        let [red, green, blue] = premultiply([srgb.red, srgb.green, srgb.blue], srgb.alpha);
+        PremultipliedSrgb::new(red, green, blue, srgb.alpha)
+    }
+}
+
+impl From<PremultipliedSrgb> for Srgb {
+    fn from(color: PremultipliedSrgb) -> Self {
+        let [red, green, blue] = unpremultiply([color.red, color.green, color.blue], color.alpha);
+        Srgb::new(red, green, blue, color.alpha)
+    }
+}
+
+impl From<LinearSrgb> for PremultipliedLinearSrgb {
+    fn from(linear: LinearSrgb) -> Self {
+        let rgb = [linear.red, linear.green, linear.blue];
+        let [red, green, blue] = premultiply(rgb, linear.alpha);
+        PremultipliedLinearSrgb::new(red, green, blue, linear.alpha)
+    }
+}
+
+impl From<PremultipliedLinearSrgb> for LinearSrgb {
+    fn from(color: PremultipliedLinearSrgb) -> Self {
+        let [red, green, blue] = unpremultiply([color.red, color.green, color.blue], color.alpha);
+        LinearSrgb::new(red, green, blue, color.alpha)
+    }
+}
+
+impl From<Srgb> for PremultipliedLinearSrgb {
+    fn from(srgb: Srgb) -> Self {
+        PremultipliedLinearSrgb::from(LinearSrgb::from(srgb))
+    }
+}
+
+impl From<PremultipliedLinearSrgb> for Srgb {
+    fn from(color: PremultipliedLinearSrgb) -> Self {
+        Srgb::from(LinearSrgb::from(color))
+    }
+}
+
+impl ColorSpace for PremultipliedSrgb {
+    fn to_xyz(self) -> XyzD65 {
+        Srgb::from(self).to_xyz()
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        PremultipliedSrgb::from(Srgb::from_xyz(xyz))
+    }
+}
+
+impl ColorSpace for PremultipliedLinearSrgb {
+    fn to_xyz(self) -> XyzD65 {
+        LinearSrgb::from(self).to_xyz()
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        PremultipliedLinearSrgb::from(LinearSrgb::from_xyz(xyz))
+    }
+}
@@ -0,0 +1,366 @@
+use crate::gradient::{split_top_level, trim};
+use crate::{
+    consume_byte, is_name, normalize_hue, parse_css_color, parse_hue, parse_number, Hsl, Hwb,
+    ParseColorError, Srgb,
+};
+use std::collections::HashMap;
+
+/// Channel values for [`Srgb::adjust`], [`Srgb::scale`] and [`Srgb::change`], following the Sass
+/// `color.adjust()`, `color.scale()` and `color.change()` functions.
+///
+/// Channels use the same units as the rest of this crate: red, green, blue, saturation,
+/// lightness, whiteness, blackness and alpha go from 0 to 1 rather than to 255 or 100%, and hue
+/// is in degrees. RGB, HSL and HWB channels cannot be combined in one call.
+///
+/// <https://sass-lang.com/documentation/modules/color>
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ColorChannels {
+    /// The red channel.
+    pub red: Option<f32>,
+    /// The green channel.
+    pub green: Option<f32>,
+    /// The blue channel.
+    pub blue: Option<f32>,
+    /// The HSL or HWB hue in degrees. Hue cannot be scaled.
+    pub hue: Option<f32>,
+    /// The HSL saturation.
+    pub saturation: Option<f32>,
+    /// The HSL lightness.
+    pub lightness: Option<f32>,
+    /// The HWB whiteness.
+    pub whiteness: Option<f32>,
+    /// The HWB blackness.
+    pub blackness: Option<f32>,
+    /// The alpha channel.
+    pub alpha: Option<f32>,
+}
+
+/// The legacy color model that a [`ColorChannels`] refers to.
+enum Model {
+    /// Only the alpha channel is given.
+    Alpha,
+    Rgb,
+    Hsl,
+    Hwb,
+}
+
+impl ColorChannels {
+    fn model(&self) -> Option<Model> {
+        let rgb = self.red.is_some() || self.green.is_some() || self.blue.is_some();
+        let hsl = self.saturation.is_some() || self.lightness.is_some();
+        let hwb = self.whiteness.is_some() || self.blackness.is_some();
+        match (rgb, hsl, hwb) {
+            (false, false, false) if self.hue.is_none() => Some(Model::Alpha),
+            (true, false, false) if self.hue.is_none() => Some(Model::Rgb),
+            (false, _, false) => Some(Model::Hsl),
+            (false, false, true) => Some(Model::Hwb),
+            _ => None,
+        }
+    }
+
+    /// Applies `f` to each channel of the color that has a value, along with the channel's
+    /// range. The hue has no range.
+    fn apply(&self, color: Srgb, f: impl Fn(f32, f32, Option<(f32, f32)>) -> f32) -> Option<Srgb> {
+        let unit = Some((0., 1.));
+        let update = |value: &mut f32, amount: Option<f32>, range| {
+            if let Some(amount) = amount {
+                *value = f(*value, amount, range);
+            }
+        };
+        let mut color = match self.model()? {
+            Model::Alpha => color,
+            Model::Rgb => {
+                let mut rgb = color;
+                update(&mut rgb.red, self.red, unit);
+                update(&mut rgb.green, self.green, unit);
+                update(&mut rgb.blue, self.blue, unit);
+                rgb
+            }
+            Model::Hsl => {
+                let mut hsl = Hsl::from(color);
+                update(&mut hsl.hue, self.hue, None);
+                update(&mut hsl.saturation, self.saturation, unit);
+                update(&mut hsl.lightness, self.lightness, unit);
+                Srgb::from(hsl)
+            }
+            Model::Hwb => {
+                let mut hwb = Hwb::from(color);
+                update(&mut hwb.hue, self.hue, None);
+                update(&mut hwb.whiteness, self.whiteness, unit);
+                update(&mut hwb.blackness, self.blackness, unit);
+                Srgb::from(hwb)
+            }
+        };
+        update(&mut color.alpha, self.alpha, unit);
+        Some(color)
+    }
+}
+
+impl Srgb {
+    /// Adds the given amounts to channels of the color, like Sass `color.adjust()`.
+    ///
+    /// Results are clamped to the range of each channel. Returns `None` if channels of different
+    /// color models are combined.
+    pub fn adjust(&self, channels: &ColorChannels) -> Option<Srgb> {
+        channels.apply(*self, |value, amount, range| match range {
+            Some((min, max)) => (value + amount).clamp(min, max),
+            None => normalize_hue(value + amount),
+        })
+    }
+
+    /// Scales channels of the color by the given fractions of the distance to their maximum, or
+    /// to their minimum for negative fractions, like Sass `color.scale()`.
+    ///
+    /// Fractions go from -1 to 1. Returns `None` if channels of different color models are
+    /// combined or if the hue is given.
+    pub fn scale(&self, channels: &ColorChannels) -> Option<Srgb> {
+        if channels.hue.is_some() {
+            return None;
+        }
+        channels.apply(*self, |value, amount, range| {
+            let (min, max) = range.unwrap_or((0., 1.));
+            let amount = amount.clamp(-1., 1.);
+            let scaled = if amount > 0. {
+                value + (max - value) * amount
+            } else {
+                value + (value - min) * amount
+            };
+            scaled.clamp(min, max)
+        })
+    }
+
+    /// Replaces channels of the color with the given values, like Sass `color.change()`.
+    ///
+    /// Returns `None` if channels of different color models are combined.
+    pub fn change(&self, channels: &ColorChannels) -> Option<Srgb> {
+        channels.apply(*self, |_, value, range| match range {
+            Some((min, max)) => value.clamp(min, max),
+            None => normalize_hue(value),
+        })
+    }
+
+    /// Increases the HSL lightness by an absolute amount, like Less and Sass `lighten()`.
+    pub fn lighten(&self, amount: f32) -> Srgb {
+        let mut hsl = Hsl::from(*self);
+        hsl.lightness = (hsl.lightness + amount).clamp(0., 1.);
+        Srgb::from(hsl)
+    }
+
+    /// Decreases the HSL lightness by an absolute amount, like Less and Sass `darken()`.
+    pub fn darken(&self, amount: f32) -> Srgb {
+        self.lighten(-amount)
+    }
+
+    /// Increases the HSL saturation by an absolute amount, like Less and Sass `saturate()`.
+    pub fn saturate(&self, amount: f32) -> Srgb {
+        let mut hsl = Hsl::from(*self);
+        hsl.saturation = (hsl.saturation + amount).clamp(0., 1.);
+        Srgb::from(hsl)
+    }
+
+    /// Decreases the HSL saturation by an absolute amount, like Less and Sass `desaturate()`.
+    pub fn desaturate(&self, amount: f32) -> Srgb {
+        self.saturate(-amount)
+    }
+
+    /// Removes all saturation, like Less `greyscale()` and Sass `grayscale()`.
+    pub fn greyscale(&self) -> Srgb {
+        self.desaturate(1.)
+    }
+
+    /// Rotates the HSL hue by an angle in degrees, like Less `spin()` and Sass `adjust-hue()`.
+    pub fn spin(&self, degrees: f32) -> Srgb {
+        let mut hsl = Hsl::from(*self);
+        hsl.hue = normalize_hue(hsl.hue + degrees);
+        Srgb::from(hsl)
+    }
+
+    /// Sets the alpha, like Less `fade()`.
+    pub fn fade(&self, alpha: f32) -> Srgb {
+        Srgb::new(self.red, self.green, self.blue, alpha.clamp(0., 1.))
+    }
+
+    /// Mixes the color with another, like Less and Sass `mix()`.
+    ///
+    /// `weight` is the proportion of this color, from 0 to 1. Unlike CSS `color-mix()`, the
+    /// weights of the RGB channels are shifted towards the more opaque color.
+    pub fn mix(&self, other: Srgb, weight: f32) -> Srgb {
+        let p = weight.clamp(0., 1.);
+        let w = p * 2. - 1.;
+        let a = self.alpha - other.alpha;
+        let w1 = if w * a == -1. {
+            (w + 1.) / 2.
+        } else {
+            ((w + a) / (1. + w * a) + 1.) / 2.
+        };
+        let w2 = 1. - w1;
+        Srgb::new(
+            self.red * w1 + other.red * w2,
+            self.green * w1 + other.green * w2,
+            self.blue * w1 + other.blue * w2,
+            self.alpha * p + other.alpha * (1. - p),
+        )
+    }
+
+    /// Mixes the color with white, like Less `tint()`. `weight` is the proportion of white.
+    pub fn tint(&self, weight: f32) -> Srgb {
+        Srgb::new(1., 1., 1., 1.).mix(*self, weight)
+    }
+
+    /// Mixes the color with black, like Less `shade()`. `weight` is the proportion of black.
+    pub fn shade(&self, weight: f32) -> Srgb {
+        Srgb::new(0., 0., 0., 1.).mix(*self, weight)
+    }
+}
+
+/// Evaluates Sass and Less color expressions, such as `darken(#336699, 10%)` or
+/// `fade(@accent, 50%)`.
+///
+/// An expression is a CSS color, a `$` or `@` variable, or a call to one of the functions
+/// `lighten`, `darken`, `saturate`, `desaturate`, `spin`, `adjust-hue`, `fade`, `fadein`,
+/// `fadeout`, `fade-in`, `fade-out`, `opacify`, `transparentize`, `mix`, `tint`, `shade`,
+/// `greyscale`, `grayscale`, `rgb` and `rgba`, whose color arguments are expressions in turn.
+/// Unitless amounts are percentages, except for the alpha amounts of the Sass functions `rgba`,
+/// `fade-in`, `fade-out`, `opacify` and `transparentize`, which go from 0 to 1.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Preprocessor {
+    /// Color variables by name, without the `$` or `@` sigil. As in Sass, hyphens and
+    /// underscores are interchangeable when variables are looked up, so `$brand-primary` finds
+    /// `brand_primary`.
+    pub variables: HashMap<String, Srgb>,
+}
+
+impl Preprocessor {
+    pub fn new() -> Preprocessor {
+        Preprocessor::default()
+    }
+
+    /// Evaluates an expression to a color.
+    pub fn evaluate(&self, s: &str) -> Result<Srgb, ParseColorError> {
+        self.evaluate_expression(s.as_bytes(), 0)
+            .map_err(|_| ParseColorError)
+    }
+
+    fn evaluate_expression(&self, input: &[u8], depth: usize) -> Result<Srgb, ()> {
+        if depth > MAX_DEPTH {
+            return Err(());
+        }
+        let input = trim(input);
+        if let Some((b'$', name)) | Some((b'@', name)) = input.split_first() {
+            if name.is_empty() || !name.iter().all(|c| is_name(*c)) {
+                return Err(());
+            }
+            let name = std::str::from_utf8(name).map_err(|_| ())?;
+            return self.variable(name).ok_or(());
+        }
+        if let Ok(color) = parse_css_color(input) {
+            return Ok(color);
+        }
+
+        let open = input.iter().position(|c| *c == b'(').ok_or(())?;
+        let (name, arguments) = (&input[..open], &input[open + 1..]);
+        let arguments = match arguments.split_last() {
+            Some((b')', arguments)) => split_top_level(arguments, |c| c == b',')?,
+            _ => return Err(()),
+        };
+        let name = std::str::from_utf8(name)
+            .map_err(|_| ())?
+            .to_ascii_lowercase();
+        let color = |i: usize| self.evaluate_expression(arguments.get(i).ok_or(())?, depth + 1);
+        let percentage = |i: usize, default: Option<f32>| match arguments.get(i) {
+            Some(argument) => parse_amount(argument, 100.),
+            None => default.ok_or(()),
+        };
+        let alpha = |i: usize| parse_amount(arguments.get(i).ok_or(())?, 1.);
+        let arity = |n: usize| {
+            if arguments.len() == n {
+                Ok(())
+            } else {
+                Err(())
+            }
+        };
+
+        match &*name {
+            "lighten" | "darken" | "saturate" | "desaturate" | "fade" | "fadein" | "fadeout" => {
+                arity(2)?;
+                let (color, amount) = (color(0)?, percentage(1, None)?);
+                Ok(match &*name {
+                    "lighten" => color.lighten(amount),
+                    "darken" => color.darken(amount),
+                    "saturate" => color.saturate(amount),
+                    "desaturate" => color.desaturate(amount),
+                    "fade" => color.fade(amount),
+                    "fadein" => color.fade(color.alpha + amount),
+                    _ => color.fade(color.alpha - amount),
+                })
+            }
+            "fade-in" | "opacify" | "fade-out" | "transparentize" => {
+                arity(2)?;
+                let (color, amount) = (color(0)?, alpha(1)?);
+                Ok(match &*name {
+                    "fade-in" | "opacify" => color.fade(color.alpha + amount),
+                    _ => color.fade(color.alpha - amount),
+                })
+            }
+            "rgb" | "rgba" => {
+                arity(2)?;
+                Ok(color(0)?.fade(alpha(1)?))
+            }
+            "spin" | "adjust-hue" => {
+                arity(2)?;
+                let angle = match parse_hue(arguments[1])? {
+                    (b"", angle) => angle,
+                    _ => return Err(()),
+                };
+                Ok(color(0)?.spin(angle))
+            }
+            "mix" => {
+                if !(2..=3).contains(&arguments.len()) {
+                    return Err(());
+                }
+                Ok(color(0)?.mix(color(1)?, percentage(2, Some(0.5))?))
+            }
+            "tint" | "shade" => {
+                if !(1..=2).contains(&arguments.len()) {
+                    return Err(());
+                }
+                let (color, weight) = (color(0)?, percentage(1, Some(0.5))?);
+                Ok(if name == "tint" {
+                    color.tint(weight)
+                } else {
+                    color.shade(weight)
+                })
+            }
+            "greyscale" | "grayscale" => {
+                arity(1)?;
+                Ok(color(0)?.greyscale())
+            }
+            _ => Err(()),
+        }
+    }
+
+    fn variable(&self, name: &str) -> Option<Srgb> {
+        let normalize = |c| if c == '_' { '-' } else { c };
+        self.variables.get(name).copied().or_else(|| {
+            self.variables
+                .iter()
+                .find(|(key, _)| key.chars().map(normalize).eq(name.chars().map(normalize)))
+                .map(|(_, color)| *color)
+        })
+    }
+}
+
+/// The maximum nesting of function calls in an expression, so that deeply nested input cannot
+/// overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+/// Parses a number or percentage, dividing numbers by `scale` so that they are a fraction.
+fn parse_amount(input: &[u8], scale: f32) -> Result<f32, ()> {
+    let (input, value) = parse_number(input)?;
+    match consume_byte(input, b'%') {
+        Ok(b"") => Ok(value / 100.),
+        Ok(_) => Err(()),
+        Err(()) if input.is_empty() => Ok(value / scale),
+        Err(()) => Err(()),
+    }
+}
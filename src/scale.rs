@@ -0,0 +1,281 @@
+use crate::interpolate::{fixup_hues, lerp};
+use crate::space::ColorSpace;
+use crate::{normalize_hue, HueInterpolation, InterpolationSpace, Oklab, ParseColorError, Srgb};
+use std::str::FromStr;
+
+/// How a [`ColorScale`] interpolates between its colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScaleInterpolation {
+    /// Piecewise linear interpolation between adjacent colors.
+    Linear,
+    /// A uniform cubic B-spline. The curve is smooth but only passes through the first and last
+    /// colors.
+    Basis,
+    /// A uniform Catmull-Rom spline. The curve is smooth and passes through every color.
+    CatmullRom,
+}
+
+impl Default for ScaleInterpolation {
+    fn default() -> Self {
+        ScaleInterpolation::Linear
+    }
+}
+
+/// A continuous scale through a list of colors.
+///
+/// The colors are evenly spaced over the domain. Samples are not gamut mapped; use
+/// [`ColorSpace::to_gamut`] to display them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorScale {
+    /// The colors of the scale.
+    pub colors: Vec<Srgb>,
+    /// The values mapped to the first and last colors. Values outside the domain are clamped.
+    pub domain: [f32; 2],
+    /// How to interpolate between colors.
+    pub interpolation: ScaleInterpolation,
+    /// The color space in which to interpolate.
+    pub space: InterpolationSpace,
+    /// How to interpolate hues in cylindrical color spaces.
+    pub hue: HueInterpolation,
+    /// Whether to adjust positions so that Oklab lightness changes linearly from the first to the
+    /// last color.
+    pub correct_lightness: bool,
+    /// The number of bins to quantize the scale into. If `None`, the scale is continuous.
+    pub classes: Option<usize>,
+}
+
+impl ColorScale {
+    /// Creates a linear scale in Oklab over the domain 0 to 1.
+    pub fn new(colors: Vec<Srgb>) -> ColorScale {
+        ColorScale {
+            colors,
+            domain: [0., 1.],
+            interpolation: ScaleInterpolation::Linear,
+            space: InterpolationSpace::Oklab,
+            hue: HueInterpolation::Shorter,
+            correct_lightness: false,
+            classes: None,
+        }
+    }
+
+    /// Creates a scale from CSS color strings.
+    pub fn parse<S: AsRef<str>>(colors: &[S]) -> Result<ColorScale, ParseColorError> {
+        let colors = colors
+            .iter()
+            .map(|color| Srgb::from_str(color.as_ref()))
+            .collect::<Result<_, _>>()?;
+        Ok(ColorScale::new(colors))
+    }
+
+    /// Returns the color at the given value of the domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scale has no colors.
+    pub fn sample(&self, value: f32) -> Srgb {
+        self.sample_with(&self.control_points(), value)
+    }
+
+    /// Returns `n` colors evenly spaced over the domain.
+    pub fn colors(&self, n: usize) -> Vec<Srgb> {
+        let [start, end] = self.domain;
+        let points = self.control_points();
+        (0..n)
+            .map(|i| {
+                let t = if n == 1 {
+                    0.
+                } else {
+                    i as f32 / (n - 1) as f32
+                };
+                self.sample_with(&points, lerp(start, end, t))
+            })
+            .collect()
+    }
+
+    fn sample_with(&self, points: &[[f32; 4]], value: f32) -> Srgb {
+        assert!(!self.colors.is_empty(), "color scale has no colors");
+        let [start, end] = self.domain;
+        let mut t = if start == end {
+            0.
+        } else {
+            ((value - start) / (end - start)).clamp(0., 1.)
+        };
+        if let Some(classes) = self.classes {
+            t = quantize(t, classes);
+        }
+        if self.correct_lightness {
+            t = self.correct(points, t);
+        }
+        self.sample_unit(points, t)
+    }
+
+    /// Samples the scale at `t` between 0 and 1, ignoring the domain and adjustments.
+    fn sample_unit(&self, points: &[[f32; 4]], t: f32) -> Srgb {
+        let n = self.colors.len();
+        if n == 1 {
+            return self.colors[0];
+        }
+        let segments = (n - 1) as f32;
+        let i = ((t * segments) as usize).min(n - 2);
+        let local = t * segments - i as f32;
+        match self.interpolation {
+            ScaleInterpolation::Linear => {
+                self.colors[i].interpolate(self.colors[i + 1], local, self.space, self.hue)
+            }
+            ScaleInterpolation::Basis => self.sample_spline(points, i, local, basis),
+            ScaleInterpolation::CatmullRom => self.sample_spline(points, i, local, catmull_rom),
+        }
+    }
+
+    fn sample_spline(
+        &self,
+        points: &[[f32; 4]],
+        i: usize,
+        t: f32,
+        spline: fn(f32, [f32; 4]) -> f32,
+    ) -> Srgb {
+        let n = points.len();
+        let mut result = [0.; 4];
+        for (c, value) in result.iter_mut().enumerate() {
+            let v1 = points[i][c];
+            let v2 = points[i + 1][c];
+            // Endpoints are extended by reflection.
+            let v0 = if i > 0 {
+                points[i - 1][c]
+            } else {
+                2. * v1 - v2
+            };
+            let v3 = if i + 2 < n {
+                points[i + 2][c]
+            } else {
+                2. * v2 - v1
+            };
+            *value = spline(t, [v0, v1, v2, v3]);
+        }
+
+        let hue_index = self.space.hue_index();
+        let alpha = result[3].clamp(0., 1.);
+        let mut components = [0.; 3];
+        for (c, component) in components.iter_mut().enumerate() {
+            *component = if Some(c) == hue_index {
+                normalize_hue(result[c])
+            } else if alpha == 0. {
+                result[c]
+            } else {
+                result[c] / alpha
+            };
+        }
+        Srgb::from_xyz(self.space.components_to_xyz(components, alpha))
+    }
+
+    /// Converts the colors to premultiplied components in the interpolation space, followed by
+    /// alpha. Missing hues are filled from neighboring colors and hues are unwrapped so that
+    /// consecutive hues follow the hue interpolation method.
+    ///
+    /// They are only needed by splines, so linear scales get none.
+    fn control_points(&self) -> Vec<[f32; 4]> {
+        if self.interpolation == ScaleInterpolation::Linear {
+            return Vec::new();
+        }
+        let interpolants: Vec<_> = self
+            .colors
+            .iter()
+            .map(|color| self.space.to_interpolant(color.to_xyz()))
+            .collect();
+        let mut points: Vec<[f32; 4]> = interpolants
+            .iter()
+            .map(|color| {
+                let [c0, c1, c2] = color.components;
+                [c0, c1, c2, color.alpha]
+            })
+            .collect();
+
+        if let Some(h) = self.space.hue_index() {
+            // A missing hue takes the previous hue, or the next one at the start of the scale.
+            let first = interpolants
+                .iter()
+                .find(|color| !color.hue_missing)
+                .map_or(0., |color| color.components[h]);
+            let mut previous = first;
+            for (point, color) in points.iter_mut().zip(&interpolants) {
+                if color.hue_missing {
+                    point[h] = previous;
+                }
+                previous = point[h];
+            }
+            for i in 1..points.len() {
+                let (h1, h2) = fixup_hues(points[i - 1][h], points[i][h], self.hue);
+                points[i][h] = points[i - 1][h] + (h2 - h1);
+            }
+        }
+
+        for point in &mut points {
+            let alpha = point[3];
+            for (c, component) in point[..3].iter_mut().enumerate() {
+                if Some(c) != self.space.hue_index() {
+                    *component *= alpha;
+                }
+            }
+        }
+        points
+    }
+
+    /// Finds the position whose Oklab lightness is linearly interpolated between the lightness
+    /// of the first and last colors.
+    fn correct(&self, points: &[[f32; 4]], t: f32) -> f32 {
+        let lightness = |t| self.sample_unit(points, t).convert::<Oklab>().lightness;
+        let (l0, l1) = (lightness(0.), lightness(1.));
+        if (l1 - l0).abs() < LIGHTNESS_EPSILON {
+            return t;
+        }
+        let target = lerp(l0, l1, t);
+        let (mut low, mut high, mut mid) = (0., 1., t);
+        for _ in 0..LIGHTNESS_ITERATIONS {
+            let l = lightness(mid);
+            if (l - target).abs() < LIGHTNESS_EPSILON {
+                break;
+            }
+            if (l < target) == (l0 < l1) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+            mid = (low + high) / 2.;
+        }
+        mid
+    }
+}
+
+const LIGHTNESS_EPSILON: f32 = 0.0001;
+const LIGHTNESS_ITERATIONS: usize = 20;
+
+/// Divides the unit interval into `classes` equal bins and maps `t` to the position of its bin,
+/// from 0 for the first bin to 1 for the last.
+fn quantize(t: f32, classes: usize) -> f32 {
+    if classes <= 1 {
+        return 0.5;
+    }
+    let bin = ((t * classes as f32) as usize).min(classes - 1);
+    bin as f32 / (classes - 1) as f32
+}
+
+// https://en.wikipedia.org/wiki/B-spline#Cubic_B-Splines
+pub(crate) fn basis(t: f32, [v0, v1, v2, v3]: [f32; 4]) -> f32 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    ((1. - 3. * t + 3. * t2 - t3) * v0
+        + (4. - 6. * t2 + 3. * t3) * v1
+        + (1. + 3. * t + 3. * t2 - 3. * t3) * v2
+        + t3 * v3)
+        / 6.
+}
+
+// https://en.wikipedia.org/wiki/Cubic_Hermite_spline#Catmull%E2%80%93Rom_spline
+fn catmull_rom(t: f32, [v0, v1, v2, v3]: [f32; 4]) -> f32 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2. * v1
+        + (v2 - v0) * t
+        + (2. * v0 - 5. * v1 + 4. * v2 - v3) * t2
+        + (3. * v1 - v0 - 3. * v2 + v3) * t3)
+}
@@ -0,0 +1,111 @@
+use crate::difference::{delta_e, DeltaE};
+use crate::gamut::{gamut_map, RgbColorSpace};
+use crate::interpolate::{interpolate, HueInterpolation, InterpolationSpace};
+use crate::{Hsl, Hwb, Oklch, Srgb, XyzD65};
+
+/// A color space that can be converted to and from every other color space.
+///
+/// Conversions go through CIE XYZ with a D65 white point, so each color space only needs to know
+/// how to convert to and from XYZ.
+pub trait ColorSpace: Copy {
+    /// Converts the color to CIE XYZ with a D65 white point.
+    fn to_xyz(self) -> XyzD65;
+
+    /// Converts a color from CIE XYZ with a D65 white point.
+    fn from_xyz(xyz: XyzD65) -> Self;
+
+    /// Converts the color to another color space.
+    fn convert<T: ColorSpace>(self) -> T {
+        T::from_xyz(self.to_xyz())
+    }
+
+    /// Returns `true` if the color is within the gamut of an RGB color space.
+    fn in_gamut<T: RgbColorSpace>(self) -> bool {
+        self.convert::<T>().is_in_gamut()
+    }
+
+    /// Converts the color to an RGB color space by clamping out of gamut components.
+    ///
+    /// Clipping is fast but can shift the hue of colors that are far out of gamut. Prefer
+    /// [`ColorSpace::to_gamut`] when accuracy matters.
+    fn clip_to_gamut<T: RgbColorSpace>(self) -> T {
+        self.convert::<T>().clip()
+    }
+
+    /// Converts the color to an RGB color space using the CSS gamut mapping algorithm.
+    ///
+    /// Out of gamut colors have their Oklch chroma reduced, preserving lightness and hue as far as
+    /// possible.
+    ///
+    /// <https://www.w3.org/TR/css-color-4/#gamut-mapping>
+    fn to_gamut<T: RgbColorSpace>(self) -> T {
+        // Converting through Oklch loses precision, so colors already in gamut are kept.
+        let destination = self.convert::<T>();
+        if destination.is_in_gamut() {
+            destination.clip()
+        } else {
+            gamut_map(self.convert::<Oklch>())
+        }
+    }
+
+    /// Interpolates between two colors, where `t` is 0 for `self` and 1 for `other`.
+    ///
+    /// Colors are interpolated in `space` with premultiplied alpha. Only powerless hues are treated
+    /// as missing and take the hue of the other color: `none` components are parsed as 0, so they
+    /// are interpolated like any other value. Values of `t` outside 0 to 1 extrapolate, and the
+    /// result is not gamut mapped.
+    ///
+    /// <https://www.w3.org/TR/css-color-4/#interpolation>
+    fn interpolate(
+        self,
+        other: Self,
+        t: f32,
+        space: InterpolationSpace,
+        hue: HueInterpolation,
+    ) -> Self {
+        interpolate(self, other, t, space, hue)
+    }
+
+    /// Returns the difference between two colors using the given formula.
+    fn delta_e<T: ColorSpace>(self, other: T, method: DeltaE) -> f32 {
+        delta_e(self, other, method)
+    }
+
+    /// Returns `true` if the CIEDE2000 difference between two colors is at most `threshold`.
+    ///
+    /// A difference of about 1 is just noticeable when the colors are side by side, and
+    /// differences above 2 are noticeable at a glance.
+    fn approx_eq<T: ColorSpace>(self, other: T, threshold: f32) -> bool {
+        delta_e(self, other, DeltaE::Ciede2000) <= threshold
+    }
+}
+
+impl ColorSpace for Srgb {
+    fn to_xyz(self) -> XyzD65 {
+        XyzD65::from(self)
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        Srgb::from(xyz)
+    }
+}
+
+impl ColorSpace for Hsl {
+    fn to_xyz(self) -> XyzD65 {
+        XyzD65::from(Srgb::from(self))
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        Hsl::from(Srgb::from(xyz))
+    }
+}
+
+impl ColorSpace for Hwb {
+    fn to_xyz(self) -> XyzD65 {
+        XyzD65::from(Srgb::from(self))
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        Hwb::from(Srgb::from(xyz))
+    }
+}
@@ -1,6 +1,12 @@
+#![allow(clippy::excessive_precision)]
+
 mod web_platform_tests;
 
-use crate::{Rgba, Srgb};
+use crate::{
+    named, Cam16, ColorFormat, ColorSpace, DisplayP3, Hsl, Hwb, Lab, Lch, LinearSrgb, NamedAlias,
+    Notation, Oklab, Oklch, ParseOptions, PredefinedSpace, Rec2020, RgbColorSpace, Rgba, Srgb,
+    XyzD50, XyzD65,
+};
 use std::str::FromStr;
 
 #[cfg(feature = "bench")]
@@ -171,6 +177,252 @@ fn rgba_srgb_type_compat() {
     };
 }
 
+#[test]
+fn hsl_type() {
+    let hsl = Hsl::from_str("hsl(270deg 50% 40% / 0.5)").unwrap();
+    assert_eq!(Hsl::new(270., 0.5, 0.4, 0.5), hsl);
+    assert_eq!(
+        Hsl::new(270., 0.5, 0.4, 1.),
+        Hsl::from_str("hsla(0.75turn, 50%, 40%)").unwrap()
+    );
+    assert_eq!(
+        Hsl::new(350., 0., 0., 1.),
+        Hsl::from_str("hsl(-10 none 0)").unwrap()
+    );
+    assert!(Hsl::from_str("rgb(0 0 0)").is_err());
+    assert!(Hsl::from_str("#000").is_err());
+    assert!(Hsl::from_str("black").is_err());
+
+    assert_color_approx_eq(
+        Srgb::from_str("rgb(102 51 153 / 0.5)").unwrap(),
+        Srgb::from(hsl),
+    );
+    let red = Hsl::from(Srgb::from_str("red").unwrap());
+    assert_eq!(Hsl::new(0., 1., 0.5, 1.), red);
+    assert!(!red.is_hue_powerless());
+
+    let gray = Hsl::from(Srgb::from_str("gray").unwrap());
+    assert_eq!(0., gray.hue);
+    assert_eq!(0., gray.saturation);
+    assert!(gray.is_hue_powerless());
+    assert!(Hsl::new(120., 1., 1., 1.).is_hue_powerless());
+
+    for (_, color) in named_colors() {
+        let hsl = Hsl::from(color);
+        assert!(0. <= hsl.hue && hsl.hue < 360.);
+        assert_color_approx_eq(color, Srgb::from(hsl));
+    }
+}
+
+#[test]
+fn hwb_type() {
+    let hwb = Hwb::from_str("hwb(270 20% 40% / 50%)").unwrap();
+    assert_eq!(Hwb::new(270., 0.2, 0.4, 0.5), hwb);
+    assert!(Hwb::from_str("hwba(270 20% 40%)").is_err());
+    assert!(Hwb::from_str("hsl(270 20% 40%)").is_err());
+
+    let red = Hwb::from(Srgb::from_str("red").unwrap());
+    assert_eq!(Hwb::new(0., 0., 0., 1.), red);
+    assert!(!red.is_hue_powerless());
+
+    let gray = Hwb::from(Srgb::from_str("#808080").unwrap());
+    assert_eq!(0., gray.hue);
+    assert!(gray.is_hue_powerless());
+
+    for (_, color) in named_colors() {
+        let hwb = Hwb::from(color);
+        assert!(0. <= hwb.hue && hwb.hue < 360.);
+        assert_color_approx_eq(color, Srgb::from(hwb));
+    }
+}
+
+#[test]
+fn linear_srgb() {
+    let color = LinearSrgb::from(Srgb::new(0., 0.5, 1., 0.5));
+    assert_eq!(0., color.red);
+    assert!((color.green - 0.214_041_14).abs() < 1e-7);
+    assert_eq!(1., color.blue);
+    assert_eq!(0.5, color.alpha);
+    assert_eq!(
+        0.04045 / 12.92,
+        LinearSrgb::from(Srgb::new(0.04045, 0., 0., 1.)).red
+    );
+
+    // Out of range values are extended symmetrically.
+    let extended = LinearSrgb::from(Srgb::new(-0.5, 1.5, 0., 1.));
+    assert_eq!(-color.green, extended.red);
+    assert!(extended.green > 1.);
+    assert_color_approx_eq(Srgb::new(-0.5, 1.5, 0., 1.), Srgb::from(extended));
+
+    for (_, color) in named_colors() {
+        assert_color_approx_eq(color, Srgb::from(LinearSrgb::from(color)));
+    }
+}
+
+#[test]
+fn xyz() {
+    #[track_caller]
+    fn assert_xyz(x: f32, y: f32, z: f32, actual: (f32, f32, f32)) {
+        assert!((x - actual.0).abs() < 1e-6, "{} != {}", x, actual.0);
+        assert!((y - actual.1).abs() < 1e-6, "{} != {}", y, actual.1);
+        assert!((z - actual.2).abs() < 1e-6, "{} != {}", z, actual.2);
+    }
+
+    let white = XyzD65::from(named::WHITE);
+    assert_xyz(0.950_455_9, 1., 1.089_057_8, (white.x, white.y, white.z));
+    let white = XyzD50::from(named::WHITE);
+    assert_xyz(0.964_295_7, 1., 0.825_104_6, (white.x, white.y, white.z));
+    let red = XyzD65::from(named::RED);
+    assert_xyz(0.412_390_8, 0.212_639, 0.019_330_8, (red.x, red.y, red.z));
+    let black = XyzD50::from(named::BLACK);
+    assert_xyz(0., 0., 0., (black.x, black.y, black.z));
+    assert_eq!(0.25, XyzD50::from(Srgb::new(1., 1., 1., 0.25)).alpha);
+
+    for (_, color) in named_colors() {
+        assert_color_approx_eq(color, Srgb::from(XyzD65::from(color)));
+        assert_color_approx_eq(color, Srgb::from(XyzD50::from(color)));
+        assert_color_approx_eq(
+            color,
+            Srgb::from(XyzD65::from(XyzD50::from(XyzD65::from(color)))),
+        );
+    }
+}
+
+#[test]
+fn color_space() {
+    #[track_caller]
+    fn assert_approx(expected: [f32; 3], actual: [f32; 3], epsilon: f32) {
+        for i in 0..3 {
+            assert!(
+                (expected[i] - actual[i]).abs() <= epsilon,
+                "{:?} != {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    let red = named::RED;
+    let lab = red.convert::<Lab>();
+    assert_approx(
+        [54.290_54, 80.804_93, 69.890_96],
+        [lab.lightness, lab.a, lab.b],
+        1e-3,
+    );
+    let lch = red.convert::<Lch>();
+    assert_approx(
+        [54.290_54, 106.837_18, 40.857_66],
+        [lch.lightness, lch.chroma, lch.hue],
+        1e-3,
+    );
+    let oklab = red.convert::<Oklab>();
+    assert_approx(
+        [0.627_955, 0.224_863, 0.125_846],
+        [oklab.lightness, oklab.a, oklab.b],
+        1e-5,
+    );
+    let oklch = red.convert::<Oklch>();
+    assert_approx(
+        [0.627_955, 0.257_683, 29.233_885],
+        [oklch.lightness, oklch.chroma, oklch.hue],
+        1e-4,
+    );
+    assert!(!oklch.is_hue_powerless());
+
+    let white = named::WHITE.convert::<Oklch>();
+    assert_approx(
+        [1., 0., 0.],
+        [white.lightness, white.chroma, white.hue],
+        1e-5,
+    );
+    assert!(white.is_hue_powerless());
+    let gray = named::GRAY.convert::<Lch>();
+    assert_eq!(0., gray.hue);
+    assert!(gray.is_hue_powerless());
+    assert_eq!(0.5, Srgb::new(1., 0., 0., 0.5).convert::<Oklab>().alpha);
+
+    let hsl = Hsl::new(270., 0.5, 0.4, 1.);
+    let hwb = hsl.convert::<Hwb>();
+    assert_approx(
+        [270., 0.2, 0.4],
+        [hwb.hue, hwb.whiteness, hwb.blackness],
+        1e-4,
+    );
+    let linear = Oklch::new(0.7, 0.1, 180., 1.).convert::<LinearSrgb>();
+    let xyz = linear.convert::<XyzD50>();
+    assert_color_approx_eq(Srgb::from(linear), xyz.convert());
+
+    fn round_trip<T: ColorSpace>(color: Srgb) {
+        assert_color_approx_eq(color, color.convert::<T>().convert());
+    }
+    for (_, color) in named_colors() {
+        round_trip::<Srgb>(color);
+        round_trip::<LinearSrgb>(color);
+        round_trip::<Hsl>(color);
+        round_trip::<Hwb>(color);
+        round_trip::<XyzD65>(color);
+        round_trip::<XyzD50>(color);
+        round_trip::<Lab>(color);
+        round_trip::<Lch>(color);
+        round_trip::<Oklab>(color);
+        round_trip::<Oklch>(color);
+        round_trip::<Cam16>(color);
+    }
+}
+
+#[test]
+fn gamut() {
+    let p3_red = DisplayP3::new(1., 0., 0., 1.);
+    assert!(p3_red.in_gamut::<DisplayP3>());
+    assert!(DisplayP3::new(0., 1., 0., 1.).in_gamut::<Rec2020>());
+    assert!(!p3_red.in_gamut::<Srgb>());
+    assert!(named::RED.in_gamut::<Srgb>());
+    assert!(named::RED.in_gamut::<DisplayP3>());
+    assert!(!Rec2020::new(0., 1., 0., 1.).in_gamut::<DisplayP3>());
+    assert!(!Srgb::new(1.1, 0., 0., 1.).in_gamut::<Srgb>());
+    for (_, color) in named_colors() {
+        assert!(color.in_gamut::<Srgb>());
+        assert!(color.in_gamut::<LinearSrgb>());
+        assert!(color.in_gamut::<DisplayP3>());
+        assert!(color.in_gamut::<Rec2020>());
+        assert_color_approx_eq(color, color.to_gamut());
+    }
+
+    let srgb = p3_red.clip_to_gamut::<Srgb>();
+    assert_eq!(Srgb::new(1., 0., 0., 1.), srgb);
+
+    // Gamut mapping keeps lightness and hue, unlike clipping.
+    for origin in [
+        p3_red,
+        DisplayP3::new(0., 1., 0., 0.5),
+        DisplayP3::new(0., 0., 1., 1.),
+        DisplayP3::new(1., 1., 0., 1.),
+        DisplayP3::new(0., 1., 1., 1.),
+    ] {
+        let mapped = origin.to_gamut::<Srgb>();
+        assert!(mapped.is_in_gamut());
+        assert_eq!(origin.alpha, mapped.alpha);
+        let clipped = origin.clip_to_gamut::<Srgb>().convert::<Oklch>();
+        let origin = origin.convert::<Oklch>();
+        let mapped = mapped.convert::<Oklch>();
+        assert!(mapped.chroma < origin.chroma);
+        assert!((mapped.lightness - origin.lightness).abs() < 0.02);
+        assert!((mapped.hue - origin.hue).abs() < 3.);
+        assert!((mapped.hue - origin.hue).abs() <= (clipped.hue - origin.hue).abs() + 0.01);
+    }
+    let mapped = Rec2020::new(0., 1., 0., 1.).to_gamut::<DisplayP3>();
+    assert!(mapped.is_in_gamut());
+
+    assert_eq!(
+        Srgb::new(1., 1., 1., 0.5),
+        Oklch::new(1.2, 0.3, 30., 0.5).to_gamut()
+    );
+    assert_eq!(
+        Srgb::new(0., 0., 0., 1.),
+        Oklch::new(-0.1, 0.3, 30., 1.).to_gamut()
+    );
+}
+
 #[test]
 fn hex() {
     assert!(Srgb::from_str("#").is_err());
@@ -569,150 +821,2122 @@ fn hsl() {
 }
 
 #[test]
-fn named() {
-    for (name, color) in named_colors() {
-        assert_eq!(color, Srgb::from_str(&name).unwrap());
-        assert_eq!(color, Srgb::from_str(&name.to_uppercase()).unwrap());
-        {
-            let mut name = name.clone();
-            name.pop();
-            assert!(Srgb::from_str(&name).is_err());
-        }
-        {
-            let mut name = name.clone();
-            name.push('a');
-            assert!(Srgb::from_str(&name).is_err());
-        }
+fn display() {
+    assert_eq!(
+        "rgb(102, 51, 153)",
+        Srgb::from_str("rebeccapurple").unwrap().to_string()
+    );
+    assert_eq!(
+        "rgba(0, 0, 0, 0)",
+        Srgb::from_str("transparent").unwrap().to_string()
+    );
+    assert_eq!(
+        "rgb(128, 0, 0)",
+        Srgb::from_str("rgb(50% 0 0)").unwrap().to_string()
+    );
+    assert_eq!("rgb(255, 0, 0)", Srgb::new(1.5, -0.5, 0., 2.).to_string());
+    assert_eq!(
+        "rgba(1, 2, 3, 0.2)",
+        Srgb::from_str("#01020333").unwrap().to_string()
+    );
+    assert_eq!(
+        "rgba(1, 2, 3, 0.5)",
+        Srgb::from_str("rgb(1 2 3 / 0.5)").unwrap().to_string()
+    );
+    assert_eq!(
+        "rgba(1, 2, 3, 0.498)",
+        Srgb::from_str("#0102037f").unwrap().to_string()
+    );
+    assert_eq!(
+        "rgba(1, 2, 3, 0.004)",
+        Srgb::from_str("#01020301").unwrap().to_string()
+    );
+    assert_eq!(
+        "rgba(1, 2, 3, 0.99)",
+        Srgb::from_str("#010203fc").unwrap().to_string()
+    );
+
+    // Serialization must round-trip every 8-bit alpha value.
+    for alpha in 0..=255 {
+        let color = Srgb::from_rgba8(0, 0, 0, alpha);
+        let serialized = Srgb::from_str(&color.to_string()).unwrap();
+        assert_eq!(alpha, color_f32_to_u8(serialized.alpha));
     }
 }
 
 #[test]
-fn numeric() {
-    assert!(Srgb::from_str("rgb(6 36 216 / 100%)").is_ok());
-    assert!(Srgb::from_str("rgb(6. 36 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36. 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216. / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216 / 100.%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216 / 100%.)").is_err());
-
-    assert!(Srgb::from_str("rgb(6.e0 36 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36.e0 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216.e0 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216 / 100.e0%)").is_err());
-
-    assert!(Srgb::from_str("rgb(6e 36 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36e 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216e / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216 / 100e%)").is_err());
-
-    assert!(Srgb::from_str("rgb(6e+ 36 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36e+ 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216e+ / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216 / 100e+%)").is_err());
-
-    assert!(Srgb::from_str("rgb(6e- 36 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36e- 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216e- / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216 / 100e-%)").is_err());
-
-    assert!(Srgb::from_str("rgb(6e+0. 36 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36e+0. 216 / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216e+0. / 100%)").is_err());
-    assert!(Srgb::from_str("rgb(6 36 216 / 100e+0.%)").is_err());
+fn format() {
+    let rebeccapurple = Srgb::from_str("rebeccapurple").unwrap();
+    let translucent = Srgb::from_str("rgb(102 51 153 / 0.5)").unwrap();
+    let gray = Srgb::from_str("rgb(50% 50% 50%)").unwrap();
+
+    let mut format = ColorFormat::new(Notation::Hex3);
+    assert_eq!("#639", format.format(rebeccapurple));
+    assert_eq!("#fff", format.format(Srgb::from_str("#f8f8f8").unwrap()));
+    format.notation = Notation::Hex4;
+    assert_eq!("#6398", format.format(translucent));
+    format.notation = Notation::Hex6;
+    assert_eq!("#663399", format.format(translucent));
+    format.notation = Notation::Hex8;
+    assert_eq!("#66339980", format.format(translucent));
+    format.uppercase = true;
+    assert_eq!(
+        "#FF00FF",
+        ColorFormat {
+            notation: Notation::Hex6,
+            ..format
+        }
+        .format(Srgb::new(1., 0., 1., 1.))
+    );
 
+    let mut format = ColorFormat::new(Notation::Rgb);
+    assert_eq!("rgb(40% 20% 60%)", format.format(rebeccapurple));
+    assert_eq!("rgb(40% 20% 60% / 0.5)", format.format(translucent));
+    format.percentage = false;
+    assert_eq!("rgb(102 51 153)", format.format(rebeccapurple));
+    format.notation = Notation::LegacyRgb;
+    assert_eq!("rgb(102, 51, 153)", format.format(rebeccapurple));
+    assert_eq!("rgba(102, 51, 153, 0.5)", format.format(translucent));
+    format.percentage = true;
+    assert_eq!("rgb(50%, 50%, 50%)", format.format(gray));
+
+    let mut format = ColorFormat::new(Notation::Hsl);
+    assert_eq!("hsl(270 50% 40%)", format.format(rebeccapurple));
+    assert_eq!("hsl(270 50% 40% / 0.5)", format.format(translucent));
+    assert_eq!("hsl(0 0% 50%)", format.format(gray));
+    format.none = true;
+    assert_eq!("hsl(none 0% 50%)", format.format(gray));
+    format.percentage = false;
+    assert_eq!("hsl(270 50 40)", format.format(rebeccapurple));
+    format.notation = Notation::LegacyHsl;
+    assert_eq!("hsl(0, 0%, 50%)", format.format(gray));
+    assert_eq!("hsla(270, 50%, 40%, 0.5)", format.format(translucent));
+
+    let mut format = ColorFormat::new(Notation::Hwb);
+    assert_eq!("hwb(270 20% 40%)", format.format(rebeccapurple));
+    assert_eq!("hwb(0 50% 50%)", format.format(gray));
+    format.none = true;
+    assert_eq!("hwb(none 50% 50%)", format.format(gray));
+
+    let mut format = ColorFormat::new(Notation::Lab);
+    assert_eq!("lab(32.39% 38.42 -47.69)", format.format(rebeccapurple));
+    format.notation = Notation::Lch;
+    assert_eq!("lch(32.39% 61.24 308.86 / 0.5)", format.format(translucent));
+    assert_eq!("lch(53.39% 0 0)", format.format(gray));
+    format.none = true;
+    assert_eq!("lch(53.39% 0 none)", format.format(gray));
+    format.notation = Notation::Oklab;
+    format.percentage = false;
+    format.precision = Some(3);
+    assert_eq!("oklab(0.628 0.225 0.126)", format.format(named::RED));
+    format.notation = Notation::Oklch;
+    assert_eq!("oklch(0.628 0.258 29.234)", format.format(named::RED));
+    assert_eq!("oklch(1 0 none)", format.format(named::WHITE));
+
+    let mut format = ColorFormat::new(Notation::Color(PredefinedSpace::DisplayP3));
+    format.precision = Some(4);
     assert_eq!(
-        Srgb {
-            red: 6. / 255.,
-            green: 0.36 / 255.,
-            blue: 216. / 255.,
-            alpha: 1.0
-        },
-        Srgb::from_str("rgb(6e+0.36+216/100%)").unwrap()
+        "color(display-p3 0.9175 0.2003 0.1386)",
+        format.format(named::RED)
+    );
+    format.notation = Notation::Color(PredefinedSpace::Srgb);
+    assert_eq!("color(srgb 0.4 0.2 0.6 / 0.5)", format.format(translucent));
+    format.notation = Notation::Color(PredefinedSpace::SrgbLinear);
+    assert_eq!("color(srgb-linear 0.214 0.214 0.214)", format.format(gray));
+    format.notation = Notation::Color(PredefinedSpace::XyzD65);
+    assert_eq!(
+        "color(xyz-d65 0.9505 1 1.0891)",
+        format.format(named::WHITE)
     );
+    format.notation = Notation::Color(PredefinedSpace::XyzD50);
+    assert_eq!(
+        "color(xyz-d50 0.9643 1 0.8251)",
+        format.format(named::WHITE)
+    );
+    format.notation = Notation::Color(PredefinedSpace::Rec2020);
+    assert_eq!("color(rec2020 1 1 1)", format.format(named::WHITE));
+
+    let color = Srgb::from_str("rgb(12.345% 0% 0%)").unwrap();
+    let mut format = ColorFormat::new(Notation::Rgb);
+    assert_eq!("rgb(12.35% 0% 0%)", format.format(color));
+    format.precision = Some(0);
+    assert_eq!("rgb(12% 0% 0%)", format.format(color));
+    format.precision = None;
+    assert_eq!(color, Srgb::from_str(&format.format(color)).unwrap(),);
+
+    // Every notation must parse back to the same color.
+    for notation in [
+        Notation::Hex8,
+        Notation::Rgb,
+        Notation::LegacyRgb,
+        Notation::Hsl,
+        Notation::LegacyHsl,
+        Notation::Hwb,
+        Notation::Color(PredefinedSpace::Srgb),
+        Notation::Color(PredefinedSpace::SrgbLinear),
+        Notation::Color(PredefinedSpace::DisplayP3),
+        Notation::Color(PredefinedSpace::Rec2020),
+        Notation::Color(PredefinedSpace::XyzD50),
+        Notation::Color(PredefinedSpace::XyzD65),
+    ] {
+        let mut format = ColorFormat::new(notation);
+        format.none = true;
+        for (_, color) in named_colors() {
+            assert_color_approx_eq(
+                color,
+                Srgb::from_str(
+                    &ColorFormat {
+                        precision: None,
+                        ..format
+                    }
+                    .format(color),
+                )
+                .unwrap(),
+            );
+        }
+    }
+}
 
-    let nines = "9".repeat(999);
+#[test]
+fn minify() {
+    assert_eq!("red", Srgb::from_str("#ff0000").unwrap().minify());
+    assert_eq!("tan", Srgb::from_str("rgb(210 180 140)").unwrap().minify());
+    assert_eq!("#00f", Srgb::from_str("blue").unwrap().minify());
+    assert_eq!("#fff", Srgb::from_str("white").unwrap().minify());
+    assert_eq!("navy", Srgb::from_str("#000080").unwrap().minify());
+    assert_eq!("#639", Srgb::from_str("rebeccapurple").unwrap().minify());
+    assert_eq!("#123456", Srgb::from_str("#123456").unwrap().minify());
+    assert_eq!("#1234", Srgb::from_str("#11223344").unwrap().minify());
+    assert_eq!("#12345678", Srgb::from_str("#12345678").unwrap().minify());
+    assert_eq!("#0000", Srgb::from_str("transparent").unwrap().minify());
     assert_eq!(
-        Srgb {
-            red: 100. / 255.,
-            green: 100. / 255.,
-            blue: 100. / 255.,
-            alpha: 0.5445618932859895,
-        },
-        Srgb::from_str(&format!(
-            "rgb({}e-997 {}e-997 {}e-997 / 5445618932859895362967233318697132813618813095743952975439298223406969961560047552942717636670910728746893019786283454139917900193169748259349067524939840552682198095012176093045431437495773903922425632551857520884625114624126588173520906670968542074438852601438992904761759703022688483745081090292688986958251711580854575674815074162979705098246243690189880319928315307816832576838178256307401454285988871020923752587330172447966674453785790265533466496640456213871241930958703059911787722565044368663670643970181259143319016472430928902201239474588139233890135329130660705762320235358869874608541509790266400643191187286648422874774910682648288516244021893172769161449825765517353755844373640588822904791244190695299838293263075467057383813882521706545084301049855505888186560731e-781)",
-            nines, nines, nines,
-        ))
-        .unwrap()
+        "#f008",
+        Srgb::from_str("rgb(100% 0% 0% / 0.533)").unwrap().minify()
+    );
+    assert_eq!(
+        "#80808080",
+        Srgb::from_str("hsl(0 0% 50% / 50%)").unwrap().minify()
     );
 
-    assert!(Srgb::from_str("rgb(inf 0 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 inf 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 inf / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 0 / inf)").is_err());
+    for (name, color) in named_colors() {
+        let minified = color.minify();
+        assert!(minified.len() <= name.len());
+        assert_eq!(color, Srgb::from_str(&minified).unwrap());
+    }
+    for value in (0..=255).step_by(15) {
+        let color = Srgb::from_rgba8(value, 255 - value, value / 3, value);
+        let minified = color.minify();
+        assert!(minified.len() <= 9);
+        assert_eq!(color, Srgb::from_str(&minified).unwrap());
+    }
+}
 
-    assert!(Srgb::from_str("rgb(INF 0 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 INF 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 INF / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 0 / INF)").is_err());
+#[test]
+fn named_colors_table() {
+    let json: Vec<serde_json::Value> =
+        serde_json::from_str(include_str!("tests/named_colors.json")).unwrap();
+    let expected: Vec<_> = json
+        .chunks(2)
+        .map(|named| {
+            (
+                named[0].as_str().unwrap(),
+                Srgb::from_rgb8(
+                    named[1][0].as_u64().unwrap() as u8,
+                    named[1][1].as_u64().unwrap() as u8,
+                    named[1][2].as_u64().unwrap() as u8,
+                ),
+            )
+        })
+        .collect();
+    assert_eq!(expected, crate::named_colors().collect::<Vec<_>>());
+}
 
-    assert!(Srgb::from_str("rgb(infinity 0 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 infinity 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 infinity / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 0 / infinity)").is_err());
+#[test]
+fn named_constants() {
+    assert_eq!(Srgb::from_rgb8(255, 0, 0), named::RED);
+    assert_eq!(Srgb::from_rgb8(102, 51, 153), named::REBECCAPURPLE);
+    assert_eq!(
+        Srgb::from_str("rebeccapurple").unwrap(),
+        named::REBECCAPURPLE
+    );
+    assert_eq!(
+        Srgb::from_str("lightgoldenrodyellow").unwrap(),
+        named::LIGHTGOLDENRODYELLOW
+    );
+    assert_eq!(named::GRAY, named::GREY);
+    assert_eq!(Srgb::from_str("transparent").unwrap(), named::TRANSPARENT);
+}
 
-    assert!(Srgb::from_str("rgb(INFINITY 0 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 INFINITY 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 INFINITY / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 0 / INFINITY)").is_err());
+#[test]
+fn to_named() {
+    for (name, color) in crate::named_colors() {
+        let named = color.to_named().unwrap();
+        assert_eq!(color, Srgb::from_str(named).unwrap());
+        if named != name {
+            assert_eq!(Some(name), color.to_named_with(NamedAlias::Secondary));
+        }
+    }
+    let cyan = Srgb::from_str("#0ff").unwrap();
+    assert_eq!(Some("aqua"), cyan.to_named());
+    assert_eq!(Some("cyan"), cyan.to_named_with(NamedAlias::Secondary));
+    let magenta = Srgb::from_str("#f0f").unwrap();
+    assert_eq!(Some("fuchsia"), magenta.to_named());
+    assert_eq!(
+        Some("magenta"),
+        magenta.to_named_with(NamedAlias::Secondary)
+    );
+    let gray = Srgb::from_str("rgb(50% 50% 50%)").unwrap();
+    assert_eq!(Some("gray"), gray.to_named());
+    assert_eq!(Some("grey"), gray.to_named_with(NamedAlias::Secondary));
+    let red = Srgb::from_str("hsl(0 100% 50%)").unwrap();
+    assert_eq!(Some("red"), red.to_named());
+    assert_eq!(Some("red"), red.to_named_with(NamedAlias::Secondary));
+
+    assert_eq!(None, Srgb::from_str("#123456").unwrap().to_named());
+    assert_eq!(None, Srgb::from_str("#f00e").unwrap().to_named());
+    assert_eq!(None, Srgb::from_str("transparent").unwrap().to_named());
+}
 
-    assert!(Srgb::from_str("rgb(nan 0 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 nan 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 nan / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 0 / nan)").is_err());
+#[test]
+fn color_function() {
+    assert_eq!(
+        Srgb::new(0.5, 0.25, 0., 1.),
+        Srgb::from_str("color(srgb 0.5 25% none)").unwrap()
+    );
+    assert_eq!(
+        Srgb::new(1., 0., 1., 0.5),
+        Srgb::from_str("COLOR( SRGB 1 0 1 / 50% )").unwrap()
+    );
+    assert_color_approx_eq(
+        Srgb::new(0.5, 0.5, 0.5, 1.),
+        Srgb::from_str("color(srgb-linear 0.214041 0.214041 0.214041)").unwrap(),
+    );
+    assert_color_approx_eq(
+        named::WHITE,
+        Srgb::from_str("color(xyz 0.950456 1 1.089058)").unwrap(),
+    );
+    assert_color_approx_eq(
+        named::WHITE,
+        Srgb::from_str("color(xyz-d65 0.950456 1 1.089058)").unwrap(),
+    );
+    assert_color_approx_eq(
+        named::WHITE,
+        Srgb::from_str("color(xyz-d50 0.964296 1 0.825105)").unwrap(),
+    );
+    assert_color_approx_eq(
+        named::RED,
+        Srgb::from_str(&format!("color(display-p3 {})", {
+            let p3 = named::RED.convert::<DisplayP3>();
+            format!("{} {} {}", p3.red, p3.green, p3.blue)
+        }))
+        .unwrap(),
+    );
+    assert_color_approx_eq(
+        named::BLACK,
+        Srgb::from_str("color(rec2020 0 0 0)").unwrap(),
+    );
 
-    assert!(Srgb::from_str("rgb(NAN 0 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 NAN 0 / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 NAN / 0)").is_err());
-    assert!(Srgb::from_str("rgb(0 0 0 / NAN)").is_err());
+    // Out of gamut colors are gamut mapped.
+    assert_color_approx_eq(
+        DisplayP3::new(1., 0., 0., 1.).to_gamut::<Srgb>(),
+        Srgb::from_str("color(display-p3 1 0 0)").unwrap(),
+    );
+    assert!(Srgb::from_str("color(rec2020 0 1 0)")
+        .unwrap()
+        .is_in_gamut());
+
+    assert!(Srgb::from_str("color(srgb 0 0)").is_err());
+    assert!(Srgb::from_str("color(srgb 0 0 0 0)").is_err());
+    assert!(Srgb::from_str("color(srgb 0, 0, 0)").is_err());
+    assert!(Srgb::from_str("color(srgb0 0 0 0)").is_err());
+    assert!(Srgb::from_str("color(0 0 0)").is_err());
+    assert!(Srgb::from_str("color(a98-rgb 0 0 0)").is_err());
+    assert!(Srgb::from_str("color(prophoto-rgb 0 0 0)").is_err());
+    assert!(Srgb::from_str("color(srgb 0 0 0 / )").is_err());
+    assert!(Srgb::from_str("color(srgb 0deg 0 0)").is_err());
+    assert!(Srgb::from_str("color(srgb 0 0 0").is_err());
 }
 
 #[test]
-fn overflow() {
-    // https://github.com/w3c/web-platform-tests/blob/master/2dcontext/fill-and-stroke-styles/2d.fillStyle.parse.rgb-clamp-3.html
+fn extended_range() {
+    let options = ParseOptions {
+        extended_range: true,
+    };
     assert_eq!(
-        Srgb {
-            red: 0.,
-            green: 1.,
-            blue: 0.,
-            alpha: 1.,
-        },
-        Srgb::from_str("rgb(-2147483649, 4294967298, -18446744073709551619)").unwrap()
+        Srgb::new(300. / 255., -20. / 255., 0., 1.),
+        options.parse("rgb(300 -20 0)").unwrap()
     );
-
-    let nines = "9".repeat(999);
     assert_eq!(
-        Srgb {
-            red: 1.,
-            green: 1.,
-            blue: 1.,
-            alpha: 1.
-        },
-        Srgb::from_str(&format!("rgb({}, {}, {}, {})", nines, nines, nines, nines)).unwrap()
+        Srgb::new(1.5, -0.5, 0., 1.),
+        options.parse("rgba(150%, -50%, 0%, 200%)").unwrap()
     );
     assert_eq!(
-        Srgb {
-            red: 1.,
-            green: 1.,
-            blue: 1.,
-            alpha: 1.
-        },
-        Srgb::from_str("rgb(1e+999 1e+999 1e+999 / 1e+999)").unwrap()
+        Srgb::new(1.2, 0., 0., 0.),
+        options.parse("color(srgb 1.2 0 0 / -1)").unwrap()
     );
+    let p3_red = options.parse("color(display-p3 1 0 0)").unwrap();
+    assert!(p3_red.red > 1. && p3_red.green < 0. && p3_red.blue < 0.);
+    assert_color_approx_eq(DisplayP3::new(1., 0., 0., 1.).to_gamut(), p3_red.to_gamut());
     assert_eq!(
-        Srgb {
-            red: 100. / 255.,
-            green: 100. / 255.,
-            blue: 100. / 255.,
+        Srgb::new(1.5, 1.5, 1.5, 1.),
+        options.parse("hsl(0 0% 150%)").unwrap()
+    );
+    assert_eq!(
+        Srgb::new(1.5, 0.5, 0.5, 1.),
+        options.parse("hwb(0 50% -50%)").unwrap()
+    );
+    assert_eq!(
+        Srgb::new(1., 0., 0., 1.),
+        options.parse("rgb(300 -20 0)").unwrap().clip()
+    );
+    assert!(options.parse("rgb(300 -20 0").is_err());
+
+    // The default options clamp.
+    let options = ParseOptions::default();
+    assert_eq!(
+        Srgb::from_str("rgb(300 -20 0)").unwrap(),
+        options.parse("rgb(300 -20 0)").unwrap()
+    );
+    assert_eq!(named::WHITE, options.parse("hsl(0 0% 150%)").unwrap());
+
+    for (name, color) in named_colors() {
+        assert_eq!(color, options.parse(&name).unwrap());
+        assert_eq!(
+            color,
+            ParseOptions {
+                extended_range: true
+            }
+            .parse(&name)
+            .unwrap()
+        );
+    }
+}
+
+#[test]
+fn interpolate() {
+    use crate::{HueInterpolation, InterpolationSpace};
+
+    #[track_caller]
+    fn assert_hue(hue: f32, expected: f32) {
+        let delta = (hue - expected).rem_euclid(360.);
+        assert!(delta.min(360. - delta) < 0.01, "{} != {}", hue, expected);
+    }
+
+    // https://www.w3.org/TR/css-color-4/#interpolation-alpha
+    let a = Srgb::from_str("rgb(24% 12% 98% / 0.4)").unwrap();
+    let b = Srgb::from_str("rgb(62% 26% 64% / 0.6)").unwrap();
+    assert_color_approx_eq(
+        a.interpolate(b, 0.5, InterpolationSpace::Srgb, HueInterpolation::Shorter),
+        Srgb::new(0.468, 0.204, 0.776, 0.5),
+    );
+
+    // The endpoints are returned unchanged.
+    let red = named::RED;
+    let blue = named::BLUE;
+    for space in [
+        InterpolationSpace::Srgb,
+        InterpolationSpace::SrgbLinear,
+        InterpolationSpace::DisplayP3,
+        InterpolationSpace::Rec2020,
+        InterpolationSpace::Lab,
+        InterpolationSpace::Oklab,
+        InterpolationSpace::XyzD50,
+        InterpolationSpace::XyzD65,
+        InterpolationSpace::Hsl,
+        InterpolationSpace::Hwb,
+        InterpolationSpace::Lch,
+        InterpolationSpace::Oklch,
+    ] {
+        let hue = HueInterpolation::Shorter;
+        assert_color_approx_eq(red.interpolate(blue, 0., space, hue), red);
+        assert_color_approx_eq(red.interpolate(blue, 1., space, hue), blue);
+    }
+
+    // Rectangular spaces interpolate each component linearly.
+    assert_color_approx_eq(
+        red.interpolate(
+            blue,
+            0.5,
+            InterpolationSpace::Srgb,
+            HueInterpolation::Shorter,
+        ),
+        Srgb::new(0.5, 0., 0.5, 1.),
+    );
+    assert_eq!(InterpolationSpace::default(), InterpolationSpace::Oklab);
+
+    // Hue interpolation methods.
+    let from = Oklch::new(0.7, 0.1, 30., 1.);
+    let to = Oklch::new(0.7, 0.1, 90., 1.);
+    let hue = |method| {
+        from.interpolate(to, 0.5, InterpolationSpace::Oklch, method)
+            .hue
+    };
+    assert_hue(hue(HueInterpolation::Shorter), 60.);
+    assert_hue(hue(HueInterpolation::Longer), 240.);
+    assert_hue(hue(HueInterpolation::Increasing), 60.);
+    assert_hue(hue(HueInterpolation::Decreasing), 240.);
+    let hue = |method| {
+        to.interpolate(from, 0.5, InterpolationSpace::Oklch, method)
+            .hue
+    };
+    assert_hue(hue(HueInterpolation::Shorter), 60.);
+    assert_hue(hue(HueInterpolation::Longer), 240.);
+    assert_hue(hue(HueInterpolation::Increasing), 240.);
+    assert_hue(hue(HueInterpolation::Decreasing), 60.);
+    let from = Lch::new(50., 30., 20., 1.);
+    let to = Lch::new(50., 30., 340., 1.);
+    let mixed = from.interpolate(to, 0.5, InterpolationSpace::Lch, HueInterpolation::Shorter);
+    assert_hue(mixed.hue, 0.);
+    let mixed = from.interpolate(to, 0.25, InterpolationSpace::Lch, HueInterpolation::Longer);
+    assert_hue(mixed.hue, 100.);
+
+    // Powerless hues take the hue of the other color.
+    let white = Oklch::new(1., 0., 0., 1.);
+    let blue = Oklch::new(0.5, 0.2, 264., 1.);
+    let mixed = white.interpolate(
+        blue,
+        0.5,
+        InterpolationSpace::Oklch,
+        HueInterpolation::Shorter,
+    );
+    assert_hue(mixed.hue, 264.);
+    let hsl = Hsl::new(0., 0., 0.5, 1.).interpolate(
+        Hsl::new(120., 1., 0.5, 1.),
+        0.5,
+        InterpolationSpace::Hsl,
+        HueInterpolation::Shorter,
+    );
+    assert_hue(hsl.hue, 120.);
+
+    // `none` parses as 0, so only powerless hues are missing.
+    let none = Srgb::from_str("rgb(none 0 0)").unwrap();
+    let mixed = none.interpolate(
+        Srgb::new(1., 0.5, 0., 1.),
+        0.5,
+        InterpolationSpace::Srgb,
+        HueInterpolation::Shorter,
+    );
+    assert_color_approx_eq(mixed, Srgb::new(0.5, 0.25, 0., 1.));
+    let none = Hsl::from_str("hsl(none 100% 50%)").unwrap();
+    let mixed = none.interpolate(
+        Hsl::new(120., 1., 0.5, 1.),
+        0.5,
+        InterpolationSpace::Hsl,
+        HueInterpolation::Shorter,
+    );
+    assert_hue(mixed.hue, 60.);
+
+    // Fully transparent colors don't affect the other color's components.
+    let transparent = Srgb::new(0., 0., 0., 0.);
+    let mixed = transparent.interpolate(
+        named::RED,
+        0.5,
+        InterpolationSpace::Srgb,
+        HueInterpolation::Shorter,
+    );
+    assert_color_approx_eq(mixed, Srgb::new(1., 0., 0., 0.5));
+}
+
+#[test]
+fn color_scale() {
+    use crate::{ColorScale, InterpolationSpace, ScaleInterpolation};
+
+    let mut scale = ColorScale::parse(&["red", "#00f", "rgb(0 255 0)"]).unwrap();
+    assert!(ColorScale::parse(&["red", "nope"]).is_err());
+    scale.space = InterpolationSpace::Srgb;
+    assert_color_approx_eq(scale.sample(0.), named::RED);
+    assert_color_approx_eq(scale.sample(0.25), Srgb::new(0.5, 0., 0.5, 1.));
+    assert_color_approx_eq(scale.sample(0.5), named::BLUE);
+    assert_color_approx_eq(scale.sample(1.), named::LIME);
+    // Values outside the domain are clamped.
+    assert_color_approx_eq(scale.sample(-1.), named::RED);
+    assert_color_approx_eq(scale.sample(2.), named::LIME);
+
+    // Domain mapping.
+    scale.domain = [10., 20.];
+    assert_color_approx_eq(scale.sample(15.), named::BLUE);
+    assert_color_approx_eq(scale.sample(12.5), Srgb::new(0.5, 0., 0.5, 1.));
+    let colors = scale.colors(3);
+    assert_eq!(colors.len(), 3);
+    assert_color_approx_eq(colors[0], named::RED);
+    assert_color_approx_eq(colors[1], named::BLUE);
+    assert_color_approx_eq(colors[2], named::LIME);
+
+    // Quantized bins.
+    scale.classes = Some(3);
+    assert_color_approx_eq(scale.sample(10.), named::RED);
+    assert_color_approx_eq(scale.sample(13.), named::RED);
+    assert_color_approx_eq(scale.sample(14.), named::BLUE);
+    assert_color_approx_eq(scale.sample(19.), named::LIME);
+    scale.classes = None;
+    scale.domain = [0., 1.];
+
+    // Catmull-Rom passes through every color, B-splines only through the endpoints.
+    scale.interpolation = ScaleInterpolation::CatmullRom;
+    assert_color_approx_eq(scale.sample(0.), named::RED);
+    assert_color_approx_eq(scale.sample(0.5), named::BLUE);
+    assert_color_approx_eq(scale.sample(1.), named::LIME);
+    scale.interpolation = ScaleInterpolation::Basis;
+    assert_color_approx_eq(scale.sample(0.), named::RED);
+    assert_color_approx_eq(scale.sample(1.), named::LIME);
+    assert_ne!(color_f32_to_u8(scale.sample(0.5).blue), 255);
+    // Splines through collinear points are linear.
+    let mut scale = ColorScale::new(vec![
+        Srgb::new(0., 0., 0., 1.),
+        Srgb::new(0.5, 0.5, 0.5, 1.),
+        Srgb::new(1., 1., 1., 1.),
+    ]);
+    scale.space = InterpolationSpace::Srgb;
+    for interpolation in [ScaleInterpolation::Basis, ScaleInterpolation::CatmullRom] {
+        scale.interpolation = interpolation;
+        assert_color_approx_eq(scale.sample(0.3), Srgb::new(0.3, 0.3, 0.3, 1.));
+    }
+
+    // Splines unwrap hues in cylindrical spaces.
+    let mut scale = ColorScale::new(vec![
+        Oklch::new(0.7, 0.1, 300., 1.).convert(),
+        Oklch::new(0.7, 0.1, 0., 1.).convert(),
+        Oklch::new(0.7, 0.1, 60., 1.).convert(),
+    ]);
+    scale.space = InterpolationSpace::Oklch;
+    scale.interpolation = ScaleInterpolation::CatmullRom;
+    assert!((scale.sample(0.25).convert::<Oklch>().hue - 330.).abs() < 0.1);
+
+    // Lightness correction makes Oklab lightness linear.
+    let mut scale = ColorScale::parse(&["black", "red", "yellow", "white"]).unwrap();
+    scale.correct_lightness = true;
+    for t in [0.1, 0.3, 0.5, 0.7, 0.9] {
+        let lightness = scale.sample(t).convert::<Oklab>().lightness;
+        assert!((lightness - t).abs() < 0.001, "{} {}", t, lightness);
+    }
+
+    // A single color is returned everywhere.
+    let scale = ColorScale::new(vec![named::RED]);
+    assert_eq!(scale.sample(0.7), named::RED);
+}
+
+#[test]
+fn colormaps() {
+    use crate::colormap::{self, BrewerKind, BrewerScheme, Cubehelix};
+
+    let hex = |color: Srgb| color.to_string();
+    let css = |s: &str| Srgb::from_str(s).unwrap().to_string();
+    // Reference values from matplotlib.
+    for (colormap, colors) in [
+        (
+            colormap::viridis as fn(f32) -> Srgb,
+            ["#440154", "#3b528b", "#21918c", "#5ec962", "#fde725"],
+        ),
+        (
+            colormap::magma,
+            ["#000004", "#51127c", "#b73779", "#fc8961", "#fcfdbf"],
+        ),
+        (
+            colormap::inferno,
+            ["#000004", "#57106e", "#bc3754", "#f98e09", "#fcffa4"],
+        ),
+        (
+            colormap::plasma,
+            ["#0d0887", "#7e03a8", "#cc4778", "#f89540", "#f0f921"],
+        ),
+    ] {
+        for (i, color) in colors.iter().enumerate() {
+            let index = [0., 64., 128., 192., 255.][i];
+            assert_eq!(hex(colormap(index / 255.)), css(color));
+        }
+    }
+    // Halfway between two entries of the table.
+    assert_eq!(hex(colormap::viridis(0.5 / 255.)), css("rgb(68, 2, 85)"));
+    // Reference values from d3.
+    assert_eq!(hex(colormap::cividis(0.)), css("rgb(0, 32, 81)"));
+    assert_eq!(hex(colormap::cividis(1.)), css("rgb(253, 234, 69)"));
+    assert_eq!(hex(colormap::turbo(0.)), css("#23171b"));
+    assert_eq!(hex(colormap::turbo(0.5)), css("#95fb51"));
+    assert_eq!(hex(colormap::turbo(1.)), css("#900c00"));
+    // Out of range values are clamped.
+    assert_eq!(colormap::viridis(-1.), colormap::viridis(0.));
+    assert_eq!(colormap::turbo(2.), colormap::turbo(1.));
+
+    let cubehelix = Cubehelix::default();
+    assert_eq!(cubehelix.sample(0.), Srgb::new(0., 0., 0., 1.));
+    assert_eq!(cubehelix.sample(1.), Srgb::new(1., 1., 1., 1.));
+    let gray = Cubehelix::new(0.5, -1.5, 0., 1.).sample(0.25);
+    assert_eq!(gray, Srgb::new(0.25, 0.25, 0.25, 1.));
+    // Perceived brightness increases monotonically.
+    let mut previous = 0.;
+    for i in 1..=20 {
+        let lightness = cubehelix
+            .sample(i as f32 / 20.)
+            .convert::<Oklab>()
+            .lightness;
+        assert!(lightness > previous);
+        previous = lightness;
+    }
+
+    assert_eq!(BrewerScheme::Blues.kind(), BrewerKind::Sequential);
+    assert_eq!(BrewerScheme::RdBu.kind(), BrewerKind::Diverging);
+    assert_eq!(BrewerScheme::Set1.kind(), BrewerKind::Qualitative);
+    assert_eq!(BrewerScheme::Blues.colors().len(), 9);
+    assert_eq!(BrewerScheme::Spectral.colors().len(), 11);
+    assert_eq!(BrewerScheme::Paired.colors().len(), 12);
+    assert_eq!(
+        BrewerScheme::Blues.colors()[8],
+        Srgb::from_rgb8(0x08, 0x30, 0x6b)
+    );
+    assert_eq!(hex(BrewerScheme::Blues.sample(0.)), css("#f7fbff"));
+    assert_eq!(hex(BrewerScheme::Blues.sample(1.)), css("#08306b"));
+    for scheme in [BrewerScheme::Blues, BrewerScheme::Spectral] {
+        let scale = scheme.scale();
+        for i in 0..=10 {
+            let t = i as f32 / 10.;
+            assert_color_approx_eq(scheme.sample(t), scale.sample(t));
+        }
+    }
+    // Diverging schemes are neutral in the middle.
+    assert!(BrewerScheme::RdBu.sample(0.5).convert::<Oklch>().chroma < 0.01);
+    assert_eq!(
+        BrewerScheme::Set1.sample(0.),
+        Srgb::from_rgb8(0xe4, 0x1a, 0x1c)
+    );
+    assert_eq!(
+        BrewerScheme::Set1.sample(0.99),
+        BrewerScheme::Set1.colors()[8]
+    );
+    assert_eq!(
+        BrewerScheme::Set1.sample(1.),
+        BrewerScheme::Set1.colors()[8]
+    );
+}
+
+#[test]
+fn gradient() {
+    use crate::{
+        Gradient, GradientKind, HueInterpolation, InterpolationSpace, LengthPercentage,
+        LinearDirection, Position, RadialShape, RadialSize,
+    };
+
+    let parse = |s: &str| Gradient::from_str(s).unwrap();
+
+    let gradient = parse("linear-gradient(red, blue)");
+    assert_eq!(
+        gradient.kind,
+        GradientKind::Linear {
+            direction: LinearDirection::Angle(180.)
+        }
+    );
+    assert!(!gradient.repeating);
+    assert_eq!(gradient.space, InterpolationSpace::Srgb);
+    assert_eq!(gradient.stops.len(), 2);
+    assert_eq!(gradient.sample(0., 100.), named::RED);
+    assert_color_approx_eq(gradient.sample(0.5, 100.), Srgb::new(0.5, 0., 0.5, 1.));
+    assert_eq!(gradient.sample(1., 100.), named::BLUE);
+    assert_eq!(gradient.sample(-1., 100.), named::RED);
+    assert_eq!(gradient.sample(2., 100.), named::BLUE);
+
+    // Directions.
+    let direction = |s: &str| match parse(s).kind {
+        GradientKind::Linear { direction } => direction,
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        direction("linear-gradient(45deg, red, blue)"),
+        LinearDirection::Angle(45.)
+    );
+    assert_eq!(
+        direction("linear-gradient(0.25turn, red, blue)"),
+        LinearDirection::Angle(90.)
+    );
+    assert_eq!(
+        direction("linear-gradient(0, red, blue)"),
+        LinearDirection::Angle(0.)
+    );
+    assert_eq!(
+        direction("linear-gradient(to left, red, blue)"),
+        LinearDirection::Angle(270.)
+    );
+    assert_eq!(
+        direction("linear-gradient(to top right, red, blue)"),
+        LinearDirection::Corner {
+            right: true,
+            bottom: false
+        }
+    );
+    assert_eq!(
+        direction("linear-gradient(to left bottom, red, blue)"),
+        LinearDirection::Corner {
+            right: false,
+            bottom: true
+        }
+    );
+
+    // Stop positions, double-position stops and transition hints.
+    let gradient = parse("linear-gradient(red 20%, lime 70px 50%, 60%, blue)");
+    assert_eq!(gradient.stops.len(), 4);
+    assert_eq!(
+        gradient.stops[1].position,
+        Some(LengthPercentage::Length(70.))
+    );
+    assert_eq!(gradient.stops[2].color, named::LIME);
+    assert_eq!(
+        gradient.stops[2].hint,
+        Some(LengthPercentage::Percentage(0.6))
+    );
+    assert_eq!(gradient.sample(0.1, 200.), named::RED);
+    assert_color_approx_eq(gradient.sample(0.275, 200.), Srgb::new(0.5, 0.5, 0., 1.));
+    assert_color_approx_eq(gradient.sample(0.4, 200.), named::LIME);
+    // The hint is the midpoint of the transition.
+    assert_color_approx_eq(gradient.sample(0.6, 200.), Srgb::new(0., 0.5, 0.5, 1.));
+    let color = gradient.sample(0.75, 200.);
+    assert!(color.green > 0. && color.green < 0.5);
+
+    // Stops without positions are spaced evenly, and positions may not decrease.
+    let gradient = parse("linear-gradient(red, lime, blue, white 90%, black 50%)");
+    assert_color_approx_eq(gradient.sample(0.3, 100.), named::LIME);
+    assert_color_approx_eq(gradient.sample(0.6, 100.), named::BLUE);
+    assert_eq!(gradient.sample(0.95, 100.), named::BLACK);
+
+    // Hard stops.
+    let gradient = parse("linear-gradient(red 50%, blue 50%)");
+    assert_eq!(gradient.sample(0.49, 100.), named::RED);
+    assert_eq!(gradient.sample(0.5, 100.), named::BLUE);
+
+    // Interpolation methods.
+    let gradient = parse("linear-gradient(in oklch longer hue, red, blue)");
+    assert_eq!(gradient.space, InterpolationSpace::Oklch);
+    assert_eq!(gradient.hue, HueInterpolation::Longer);
+    let gradient = parse("linear-gradient(to right in hsl, red, blue)");
+    assert_eq!(gradient.space, InterpolationSpace::Hsl);
+    assert_eq!(gradient.hue, HueInterpolation::Shorter);
+    assert_color_approx_eq(gradient.sample(0.5, 100.), named::MAGENTA);
+    let gradient = parse("linear-gradient(in srgb-linear 90deg, red, blue)");
+    assert_eq!(gradient.space, InterpolationSpace::SrgbLinear);
+    let gradient = parse("linear-gradient(red, color(srgb 0 0 1))");
+    assert_eq!(gradient.space, InterpolationSpace::Oklab);
+
+    // Repeating gradients.
+    let gradient = parse("repeating-linear-gradient(red, blue 20px)");
+    assert!(gradient.repeating);
+    assert_color_approx_eq(gradient.sample(0.25, 100.), gradient.sample(0.05, 100.));
+    assert_color_approx_eq(gradient.sample(0.1, 100.), Srgb::new(0.5, 0., 0.5, 1.));
+    // A repeating gradient of zero length renders its average color.
+    let gradient = parse("repeating-linear-gradient(in srgb, red 10px, blue 10px)");
+    assert_color_approx_eq(gradient.sample(0.3, 100.), Srgb::new(0.5, 0., 0.5, 1.));
+    let gradient = parse("repeating-linear-gradient(in srgb, red 10px, blue 10px, blue 10px)");
+    assert_color_approx_eq(gradient.sample(0.3, 100.), Srgb::new(0.25, 0., 0.75, 1.));
+    let gradient = Gradient {
+        stops: Vec::new(),
+        ..parse("linear-gradient(red, blue)")
+    };
+    assert_color_approx_eq(gradient.sample(0.5, 100.), Srgb::new(0., 0., 0., 0.));
+
+    // Stops outside sRGB are only gamut mapped after interpolation.
+    let gradient = parse("linear-gradient(in display-p3, color(display-p3 0 1 0), white)");
+    assert!(!gradient.stops[0].color.is_in_gamut());
+    let expected = gradient.stops[0]
+        .color
+        .interpolate(
+            named::WHITE,
+            0.5,
+            InterpolationSpace::DisplayP3,
+            HueInterpolation::Shorter,
+        )
+        .to_gamut();
+    assert_color_approx_eq(gradient.sample(0.5, 100.), expected);
+
+    // Colors in a box.
+    let gradient = parse("linear-gradient(to right, black, white)");
+    assert_color_approx_eq(
+        gradient.color_at(50., 10., 100., 20.),
+        Srgb::new(0.5, 0.5, 0.5, 1.),
+    );
+    let gradient = parse("linear-gradient(to bottom right, black, white)");
+    // The corners are the ends of the gradient, and the other corners are halfway.
+    assert_color_approx_eq(gradient.color_at(0., 0., 200., 100.), named::BLACK);
+    assert_color_approx_eq(gradient.color_at(200., 100., 200., 100.), named::WHITE);
+    assert_color_approx_eq(
+        gradient.color_at(200., 0., 200., 100.),
+        Srgb::new(0.5, 0.5, 0.5, 1.),
+    );
+    assert_color_approx_eq(
+        gradient.color_at(0., 100., 200., 100.),
+        Srgb::new(0.5, 0.5, 0.5, 1.),
+    );
+
+    let gradient = parse("radial-gradient(circle 10px at 20px 30%, black, white)");
+    assert_eq!(
+        gradient.kind,
+        GradientKind::Radial {
+            shape: RadialShape::Circle,
+            size: RadialSize::Explicit(
+                LengthPercentage::Length(10.),
+                LengthPercentage::Length(10.)
+            ),
+            center: Position {
+                x: LengthPercentage::Length(20.),
+                y: LengthPercentage::Percentage(0.3),
+            },
+        }
+    );
+    assert_color_approx_eq(gradient.color_at(20., 30., 100., 100.), named::BLACK);
+    assert_color_approx_eq(
+        gradient.color_at(25., 30., 100., 100.),
+        Srgb::new(0.5, 0.5, 0.5, 1.),
+    );
+    assert_color_approx_eq(gradient.color_at(20., 45., 100., 100.), named::WHITE);
+    let gradient = parse("radial-gradient(black, white)");
+    assert_eq!(
+        gradient.kind,
+        GradientKind::Radial {
+            shape: RadialShape::Ellipse,
+            size: RadialSize::FarthestCorner,
+            center: Position {
+                x: LengthPercentage::Percentage(0.5),
+                y: LengthPercentage::Percentage(0.5),
+            },
+        }
+    );
+    assert_color_approx_eq(
+        gradient.color_at(100., 25., 100., 50.),
+        gradient.color_at(50., 0., 100., 50.),
+    );
+    assert_color_approx_eq(gradient.color_at(100., 50., 100., 50.), named::WHITE);
+    let gradient = parse("radial-gradient(closest-side at 25% bottom, black, white)");
+    // A zero-sized ending shape renders the last color.
+    assert_color_approx_eq(gradient.color_at(25., 99., 100., 100.), named::WHITE);
+    assert_eq!(
+        parse("repeating-radial-gradient(ellipse 10% 20px, red, blue)").kind,
+        GradientKind::Radial {
+            shape: RadialShape::Ellipse,
+            size: RadialSize::Explicit(
+                LengthPercentage::Percentage(0.1),
+                LengthPercentage::Length(20.)
+            ),
+            center: Position {
+                x: LengthPercentage::Percentage(0.5),
+                y: LengthPercentage::Percentage(0.5),
+            },
+        }
+    );
+
+    let gradient = parse("conic-gradient(from 90deg at 0 0, red, blue 180deg, lime)");
+    assert_eq!(
+        gradient.stops[1].position,
+        Some(LengthPercentage::Length(180.))
+    );
+    assert_color_approx_eq(gradient.color_at(10., 0., 100., 100.), named::RED);
+    assert_color_approx_eq(
+        gradient.color_at(0., 10., 100., 100.),
+        Srgb::new(0.5, 0., 0.5, 1.),
+    );
+    let gradient = parse("repeating-conic-gradient(black 0 25%, white 0 50%)");
+    assert_color_approx_eq(gradient.color_at(60., 40., 100., 100.), named::BLACK);
+    assert_color_approx_eq(gradient.color_at(60., 60., 100., 100.), named::WHITE);
+    assert_color_approx_eq(gradient.color_at(40., 60., 100., 100.), named::BLACK);
+    assert_color_approx_eq(gradient.color_at(40., 40., 100., 100.), named::WHITE);
+
+    for invalid in [
+        "linear-gradient(red)",
+        "linear-gradient()",
+        "linear-gradient(red, blue",
+        "linear-gradient(red, , blue)",
+        "linear-gradient(red, 50%)",
+        "linear-gradient(50%, red, blue)",
+        "linear-gradient(red, 20%, 30%, blue)",
+        "linear-gradient(45, red, blue)",
+        "linear-gradient(to center, red, blue)",
+        "linear-gradient(to left right, red, blue)",
+        "linear-gradient(in nope, red, blue)",
+        "linear-gradient(red 1em, blue)",
+        "linear-gradient(red 1% 2% 3%, blue)",
+        "radial-gradient(circle 10%, red, blue)",
+        "radial-gradient(ellipse 10px, red, blue)",
+        "conic-gradient(red 10px, blue)",
+        "sideways-gradient(red, blue)",
+    ] {
+        assert!(Gradient::from_str(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn delta_e() {
+    use crate::DeltaE;
+
+    #[track_caller]
+    fn assert_delta(lhs: Lab, rhs: Lab, method: DeltaE, expected: f32) {
+        let delta = lhs.delta_e(rhs, method);
+        assert!(
+            (delta - expected).abs() < 0.001,
+            "{} != {}",
+            delta,
+            expected
+        );
+    }
+
+    // Test data from Sharma, Wu and Dalal.
+    // http://www2.ece.rochester.edu/~gsharma/ciede2000/
+    for (lhs, rhs, expected) in [
+        ([50., 2.6772, -79.7751], [50., 0., -82.7485], 2.0425),
+        ([50., 3.1571, -77.2803], [50., 0., -82.7485], 2.8615),
+        ([50., 2.8361, -74.0200], [50., 0., -82.7485], 3.4412),
+        ([50., 0., 0.], [50., -1., 2.], 2.3669),
+        ([50., 2.5, 0.], [73., 25., -18.], 27.1492),
+        ([50., 2.5, 0.], [61., -5., 29.], 22.8977),
+        (
+            [60.2574, -34.0099, 36.2677],
+            [60.4626, -34.1751, 39.4387],
+            1.2644,
+        ),
+    ] {
+        let lhs = Lab::new(lhs[0], lhs[1], lhs[2], 1.);
+        let rhs = Lab::new(rhs[0], rhs[1], rhs[2], 1.);
+        assert_delta(lhs, rhs, DeltaE::Ciede2000, expected);
+        assert_delta(rhs, lhs, DeltaE::Ciede2000, expected);
+    }
+
+    let lhs = Lab::new(50., 0., 0., 1.);
+    let rhs = Lab::new(60., 3., 4., 1.);
+    assert_delta(lhs, rhs, DeltaE::Cie76, 125_f32.sqrt());
+    // Lightness differences are unweighted in CIE94.
+    assert_delta(lhs, Lab::new(60., 0., 0., 1.), DeltaE::Cie94, 10.);
+    let cie94 = Lab::new(50., 40., 0., 1.).delta_e(Lab::new(50., 50., 0., 1.), DeltaE::Cie94);
+    assert!((cie94 - 10. / 2.8).abs() < 0.001);
+    let cmc = DeltaE::Cmc {
+        lightness: 2.,
+        chroma: 1.,
+    };
+    assert_delta(lhs, Lab::new(60., 0., 0., 1.), cmc, 10. / (2. * 1.0884212));
+    assert_delta(lhs, lhs, cmc, 0.);
+
+    assert_delta(
+        named::BLACK.convert(),
+        named::WHITE.convert(),
+        DeltaE::Ok,
+        1.,
+    );
+    assert!((named::RED.delta_e(named::RED, DeltaE::Ok)).abs() < 0.0001);
+
+    // Colors in different spaces can be compared.
+    let red = named::RED.convert::<Oklch>();
+    assert!(named::RED.delta_e(red, DeltaE::Ciede2000) < 0.001);
+    assert!(named::RED.approx_eq(red, 0.5));
+    assert!(named::RED.approx_eq(Srgb::from_rgb8(254, 1, 0), 1.));
+    assert!(!named::RED.approx_eq(Srgb::from_rgb8(240, 10, 0), 1.));
+}
+
+#[test]
+fn contrast() {
+    use crate::{contrast_ratio, TextSize, WcagLevel};
+
+    assert_eq!(named::BLACK.relative_luminance(), 0.);
+    assert_eq!(named::WHITE.relative_luminance(), 1.);
+    assert!((named::RED.relative_luminance() - 0.2126).abs() < 0.0001);
+    assert!((named::GRAY.relative_luminance() - 0.2158605).abs() < 0.0001);
+
+    assert_eq!(contrast_ratio(named::BLACK, named::WHITE), 21.);
+    assert_eq!(contrast_ratio(named::WHITE, named::BLACK), 21.);
+    assert_eq!(contrast_ratio(named::RED, named::RED), 1.);
+    // Reference values from the WebAIM contrast checker.
+    let ratio = contrast_ratio(Srgb::from_rgb8(0x76, 0x76, 0x76), named::WHITE);
+    assert!((ratio - 4.54).abs() < 0.01, "{}", ratio);
+    let ratio = contrast_ratio(named::BLUE, named::WHITE);
+    assert!((ratio - 8.59).abs() < 0.01, "{}", ratio);
+
+    // Semi-transparent foregrounds are composited onto the background.
+    let translucent = Srgb::new(0., 0., 0., 0.5);
+    let composited = Srgb::from_str("rgb(50% 50% 50%)").unwrap();
+    assert_eq!(
+        contrast_ratio(translucent, named::WHITE),
+        contrast_ratio(composited, named::WHITE)
+    );
+    assert_eq!(contrast_ratio(Srgb::new(0., 0., 0., 0.), named::WHITE), 1.);
+
+    assert_eq!(WcagLevel::from_ratio(21., TextSize::Normal), WcagLevel::AAA);
+    assert_eq!(WcagLevel::from_ratio(7., TextSize::Normal), WcagLevel::AAA);
+    assert_eq!(WcagLevel::from_ratio(6.9, TextSize::Normal), WcagLevel::AA);
+    assert_eq!(WcagLevel::from_ratio(4.5, TextSize::Normal), WcagLevel::AA);
+    assert_eq!(
+        WcagLevel::from_ratio(4.49, TextSize::Normal),
+        WcagLevel::Fail
+    );
+    assert_eq!(WcagLevel::from_ratio(4.5, TextSize::Large), WcagLevel::AAA);
+    assert_eq!(WcagLevel::from_ratio(3., TextSize::Large), WcagLevel::AA);
+    assert_eq!(
+        WcagLevel::from_ratio(2.99, TextSize::Large),
+        WcagLevel::Fail
+    );
+    assert!(WcagLevel::AAA > WcagLevel::AA && WcagLevel::AA > WcagLevel::Fail);
+}
+
+#[test]
+fn apca() {
+    use crate::{apca_contrast, apca_font_size};
+
+    #[track_caller]
+    fn assert_lc(text: &str, background: &str, expected: f32) {
+        let text = Srgb::from_str(text).unwrap();
+        let background = Srgb::from_str(background).unwrap();
+        let lc = apca_contrast(text, background);
+        assert!((lc - expected).abs() < 0.01, "{} != {}", lc, expected);
+    }
+
+    // Reference values from the APCA-W3 package.
+    assert_lc("#888", "#fff", 63.056469930209424);
+    assert_lc("#fff", "#888", -68.54146436644962);
+    assert_lc("#000", "#aaa", 58.146262578561334);
+    assert_lc("#aaa", "#000", -56.24113336839742);
+    assert_lc("#000", "#fff", 106.04067321268862);
+    assert_lc("#fff", "#000", -107.88473318309848);
+    // Low contrasts are clipped to zero.
+    assert_lc("#fff", "#fff", 0.);
+    assert_lc("#f8f8f8", "#fff", 0.);
+    // Semi-transparent text is composited onto the background.
+    assert_lc("rgb(0 0 0 / 0)", "#fff", 0.);
+    assert_eq!(
+        apca_contrast(Srgb::new(0., 0., 0., 0.5), named::WHITE),
+        apca_contrast(Srgb::new(0.5, 0.5, 0.5, 1.), named::WHITE)
+    );
+
+    assert_eq!(apca_font_size(90., 400), Some(16.));
+    assert_eq!(apca_font_size(-90., 400), Some(16.));
+    assert_eq!(apca_font_size(92., 400), Some(16.));
+    assert_eq!(apca_font_size(75., 700), Some(14.));
+    assert_eq!(apca_font_size(60., 440), Some(24.));
+    assert_eq!(apca_font_size(30., 100), None);
+    assert_eq!(apca_font_size(5., 900), None);
+}
+
+#[test]
+fn adjust_contrast() {
+    use crate::{apca_contrast, contrast_ratio, ContrastTarget};
+
+    let background = named::WHITE;
+    let color = Srgb::from_str("#7a9cf0").unwrap();
+    let adjusted = color
+        .adjust_contrast(background, ContrastTarget::Wcag(4.5))
+        .unwrap();
+    let ratio = contrast_ratio(adjusted, background);
+    assert!((4.5..4.55).contains(&ratio), "{}", ratio);
+    // The hue is kept and the color is darkened.
+    let (before, after) = (color.convert::<Oklch>(), adjusted.convert::<Oklch>());
+    assert!((before.hue - after.hue).abs() < 1.);
+    assert!(after.lightness < before.lightness);
+
+    // On dark backgrounds, colors are lightened.
+    let background = Srgb::from_str("#333").unwrap();
+    let adjusted = color
+        .adjust_contrast(background, ContrastTarget::Wcag(7.))
+        .unwrap();
+    assert!(contrast_ratio(adjusted, background) >= 7.);
+    assert!(adjusted.convert::<Oklch>().lightness > before.lightness);
+
+    let adjusted = color
+        .adjust_contrast(named::WHITE, ContrastTarget::Apca(75.))
+        .unwrap();
+    let lc = apca_contrast(adjusted, named::WHITE);
+    assert!((75. ..75.5).contains(&lc), "{}", lc);
+    let adjusted = color
+        .adjust_contrast(named::BLACK, ContrastTarget::Apca(-90.))
+        .unwrap();
+    assert!(apca_contrast(adjusted, named::BLACK) <= -90.);
+
+    // Colors that already pass are unchanged.
+    assert_eq!(
+        named::BLACK.adjust_contrast(named::WHITE, ContrastTarget::Wcag(7.)),
+        Some(named::BLACK)
+    );
+    // Impossible targets.
+    assert_eq!(
+        color.adjust_contrast(named::GRAY, ContrastTarget::Wcag(10.)),
+        None
+    );
+}
+
+#[test]
+fn color_vision_deficiency() {
+    use crate::{ColorVisionDeficiency, DeltaE};
+
+    let deficiencies = [
+        ColorVisionDeficiency::Protan,
+        ColorVisionDeficiency::Deutan,
+        ColorVisionDeficiency::Tritan,
+        ColorVisionDeficiency::Achromat,
+    ];
+    let red = Srgb::from_str("#e41a1c").unwrap();
+    let green = Srgb::from_str("#4daf4a").unwrap();
+    for &deficiency in &deficiencies {
+        // Grays are unchanged and normal vision is the identity.
+        for &gray in &[named::BLACK, named::GRAY, named::WHITE] {
+            assert_color_approx_eq(gray.simulate_cvd(deficiency, 1.), gray);
+            assert_color_approx_eq(gray.daltonize(deficiency, 1.), gray);
+        }
+        assert_color_approx_eq(red.simulate_cvd(deficiency, 0.), red);
+        assert_color_approx_eq(red.daltonize(deficiency, 0.), red);
+        let translucent = Srgb::new(0.2, 0.4, 0.6, 0.5);
+        assert_eq!(translucent.simulate_cvd(deficiency, 1.).alpha, 0.5);
+    }
+
+    // Achromatopsia keeps the luminance.
+    let gray = red.simulate_cvd(ColorVisionDeficiency::Achromat, 1.);
+    assert!((gray.relative_luminance() - red.relative_luminance()).abs() < 0.0001);
+    assert!((gray.red - gray.green).abs() < 0.0001 && (gray.green - gray.blue).abs() < 0.0001);
+
+    // Red and green collapse for deuteranopes, less so for deuteranomaly.
+    let deutan = ColorVisionDeficiency::Deutan;
+    let difference = |a: Srgb, b: Srgb, severity| {
+        let a = a.simulate_cvd(deutan, severity);
+        a.delta_e(b.simulate_cvd(deutan, severity), DeltaE::Ok)
+    };
+    assert!(red.delta_e(green, DeltaE::Ok) > 0.3);
+    assert!(difference(red, green, 1.) < 0.1);
+    assert!(difference(red, green, 0.5) > difference(red, green, 1.));
+    let corrected = difference(red.daltonize(deutan, 1.), green.daltonize(deutan, 1.), 1.);
+    assert!(corrected > 1.5 * difference(red, green, 1.));
+
+    // Tritanopia matches libDaltonLens.
+    let tritan = ColorVisionDeficiency::Tritan;
+    for &(color, expected) in &[
+        ("#ff0000", "#ff004e"),
+        ("#00ff00", "#79e9ff"),
+        ("#0000ff", "#006288"),
+        ("#ffff00", "#ffeef1"),
+        ("#ff00ff", "#ef667a"),
+        ("#00ffff", "#47f8ff"),
+        ("#e41a1c", "#e5034a"),
+        ("#4daf4a", "#69a2b7"),
+        ("#377eb8", "#20849e"),
+        ("#ff7f00", "#ff7388"),
+    ] {
+        let simulated = Srgb::from_str(color).unwrap().simulate_cvd(tritan, 1.);
+        assert_eq!(
+            simulated.to_string(),
+            Srgb::from_str(expected).unwrap().to_string(),
+            "{}",
+            color
+        );
+    }
+    // Both half-planes agree on the separation plane, so the simulation is continuous.
+    for &[r, g, b] in &[
+        [0.5576, 0.7802, 0.],
+        [0.2788, 0., 0.9772],
+        [0.5, 0.4, 0.7505],
+    ] {
+        let epsilon = 0.001;
+        let across = |sign: f32| {
+            let [nr, ng, nb] = [0.03901, -0.02788, -0.01113];
+            let offset = sign * epsilon;
+            let color = LinearSrgb::new(r + offset * nr, g + offset * ng, b + offset * nb, 1.);
+            LinearSrgb::from(Srgb::from(color).simulate_cvd(tritan, 1.))
+        };
+        let (above, below) = (across(1.), across(-1.));
+        assert!((above.red - below.red).abs() < 0.001, "{:?}", [r, g, b]);
+        assert!((above.green - below.green).abs() < 0.001, "{:?}", [r, g, b]);
+        assert!((above.blue - below.blue).abs() < 0.001, "{:?}", [r, g, b]);
+    }
+}
+
+#[test]
+fn compositing() {
+    use crate::{BlendSpace, CompositeOperator};
+
+    let red = named::RED;
+    let blue = named::BLUE;
+    let half_red = Srgb::new(1., 0., 0., 0.5);
+    let composite =
+        |source: Srgb, backdrop, operator| source.composite(backdrop, operator, BlendSpace::Srgb);
+
+    assert_color_approx_eq(
+        composite(half_red, blue, CompositeOperator::SourceOver),
+        Srgb::new(0.5, 0., 0.5, 1.),
+    );
+    assert_color_approx_eq(
+        composite(
+            half_red,
+            Srgb::new(0., 0., 1., 0.5),
+            CompositeOperator::SourceOver,
+        ),
+        Srgb::new(2. / 3., 0., 1. / 3., 0.75),
+    );
+    assert_color_approx_eq(
+        composite(half_red, blue, CompositeOperator::DestinationOver),
+        blue,
+    );
+    assert_color_approx_eq(
+        composite(red, Srgb::new(0., 0., 1., 0.5), CompositeOperator::SourceIn),
+        half_red,
+    );
+    assert_color_approx_eq(
+        composite(
+            red,
+            Srgb::new(0., 0., 1., 0.25),
+            CompositeOperator::SourceOut,
+        ),
+        Srgb::new(1., 0., 0., 0.75),
+    );
+    assert_color_approx_eq(
+        composite(half_red, blue, CompositeOperator::SourceAtop),
+        Srgb::new(0.5, 0., 0.5, 1.),
+    );
+    assert_color_approx_eq(
+        composite(red, blue, CompositeOperator::Xor),
+        Srgb::new(0., 0., 0., 0.),
+    );
+    assert_color_approx_eq(composite(red, blue, CompositeOperator::Copy), red);
+    assert_color_approx_eq(
+        composite(red, blue, CompositeOperator::Lighter),
+        named::FUCHSIA,
+    );
+
+    // Linear blending mixes light rather than encoded values.
+    let mixed = half_red.composite(blue, CompositeOperator::SourceOver, BlendSpace::SrgbLinear);
+    assert_color_approx_eq(mixed, Srgb::from(LinearSrgb::new(0.5, 0., 0.5, 1.)));
+}
+
+#[test]
+fn blend_modes() {
+    use crate::{BlendMode, BlendSpace};
+
+    let blend = |source: &str, backdrop: &str, mode| {
+        let source = Srgb::from_str(source).unwrap();
+        let backdrop = Srgb::from_str(backdrop).unwrap();
+        let color = source.blend(backdrop, mode, BlendSpace::Srgb);
+        [color.red, color.green, color.blue, color.alpha]
+    };
+    let assert_blend = |source, backdrop, mode, expected: [f32; 4]| {
+        let actual = blend(source, backdrop, mode);
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!(
+                (a - e).abs() < 0.0001,
+                "{:?} {:?} {:?}",
+                mode,
+                actual,
+                expected
+            );
+        }
+    };
+
+    assert_blend("#f00", "#00f", BlendMode::Normal, [1., 0., 0., 1.]);
+    assert_blend(
+        "rgb(50% 100% 0%)",
+        "rgb(50% 50% 50%)",
+        BlendMode::Multiply,
+        [0.25, 0.5, 0., 1.],
+    );
+    assert_blend(
+        "rgb(50% 100% 0%)",
+        "rgb(50% 50% 50%)",
+        BlendMode::Screen,
+        [0.75, 1., 0.5, 1.],
+    );
+    assert_blend(
+        "rgb(25% 75% 50%)",
+        "rgb(50% 50% 50%)",
+        BlendMode::Overlay,
+        [0.25, 0.75, 0.5, 1.],
+    );
+    assert_blend(
+        "rgb(50% 50% 50%)",
+        "rgb(25% 75% 50%)",
+        BlendMode::Overlay,
+        [0.25, 0.75, 0.5, 1.],
+    );
+    assert_blend(
+        "rgb(20% 80% 50%)",
+        "rgb(50% 50% 90%)",
+        BlendMode::Darken,
+        [0.2, 0.5, 0.5, 1.],
+    );
+    assert_blend(
+        "rgb(20% 80% 50%)",
+        "rgb(50% 50% 90%)",
+        BlendMode::Lighten,
+        [0.5, 0.8, 0.9, 1.],
+    );
+    assert_blend(
+        "rgb(50% 100% 0%)",
+        "rgb(50% 50% 0%)",
+        BlendMode::ColorDodge,
+        [1., 1., 0., 1.],
+    );
+    assert_blend(
+        "rgb(50% 0% 100%)",
+        "rgb(50% 50% 100%)",
+        BlendMode::ColorBurn,
+        [0., 0., 1., 1.],
+    );
+    assert_blend(
+        "rgb(25% 75% 50%)",
+        "rgb(50% 50% 50%)",
+        BlendMode::HardLight,
+        [0.25, 0.75, 0.5, 1.],
+    );
+    assert_blend(
+        "rgb(100% 0% 50%)",
+        "rgb(50% 50% 50%)",
+        BlendMode::SoftLight,
+        [0.70710677, 0.25, 0.5, 1.],
+    );
+    assert_blend(
+        "rgb(100% 0% 50%)",
+        "rgb(20% 20% 20%)",
+        BlendMode::SoftLight,
+        [0.448, 0.04, 0.2, 1.],
+    );
+    assert_blend("#fff", "#f00", BlendMode::Difference, [0., 1., 1., 1.]);
+    assert_blend(
+        "rgb(50% 50% 50%)",
+        "#f00",
+        BlendMode::Exclusion,
+        [0.5, 0.5, 0.5, 1.],
+    );
+
+    // Non-separable modes.
+    assert_blend(
+        "#f00",
+        "#808080",
+        BlendMode::Hue,
+        blend("#808080", "#000", BlendMode::Normal),
+    );
+    assert_blend(
+        "#808080",
+        "#f00",
+        BlendMode::Saturation,
+        [0.3, 0.3, 0.3, 1.],
+    );
+    assert_blend("#fff", "#f00", BlendMode::Luminosity, [1., 1., 1., 1.]);
+    assert_blend("#808080", "#f00", BlendMode::Color, [0.3, 0.3, 0.3, 1.]);
+    let color = blend("#00f", "#808080", BlendMode::Color);
+    assert!((0.3 * color[0] + 0.59 * color[1] + 0.11 * color[2] - 128. / 255.).abs() < 0.0001);
+    assert!(color[2] > color[0]);
+
+    // Over a transparent backdrop, the source is unchanged.
+    assert_blend(
+        "rgb(20% 40% 60% / 50%)",
+        "transparent",
+        BlendMode::Multiply,
+        [0.2, 0.4, 0.6, 0.5],
+    );
+    // Over a translucent backdrop, the blend is partial.
+    assert_blend(
+        "#fff",
+        "rgb(0% 0% 0% / 50%)",
+        BlendMode::Multiply,
+        [0.5, 0.5, 0.5, 1.],
+    );
+}
+
+#[test]
+fn premultiplied() {
+    use crate::{PremultipliedLinearSrgb, PremultipliedSrgb};
+
+    let color = Srgb::new(0.8, 0.4, 0.2, 0.5);
+    let premultiplied = PremultipliedSrgb::from(color);
+    assert_eq!(premultiplied, PremultipliedSrgb::new(0.4, 0.2, 0.1, 0.5));
+    assert_eq!(Srgb::from(premultiplied), color);
+
+    // Round trips are exact for a range of colors and alphas.
+    for &alpha in &[1., 0.75, 0.5, 0.2, 0.1] {
+        for i in 0..=255 {
+            let value = i as f32 / 255.;
+            let color = Srgb::new(value, 1. - value, 0.5, alpha);
+            assert_color_approx_eq(Srgb::from(PremultipliedSrgb::from(color)), color);
+        }
+    }
+
+    // Fully transparent colors lose their color.
+    let transparent = PremultipliedSrgb::from(Srgb::new(1., 0.5, 0., 0.));
+    assert_eq!(transparent, PremultipliedSrgb::new(0., 0., 0., 0.));
+    assert_eq!(Srgb::from(transparent), Srgb::new(0., 0., 0., 0.));
+    assert_eq!(
+        LinearSrgb::from(PremultipliedLinearSrgb::new(0., 0., 0., 0.)),
+        LinearSrgb::new(0., 0., 0., 0.)
+    );
+
+    // Linear premultiplication happens after decoding.
+    let linear = PremultipliedLinearSrgb::from(Srgb::new(0.5, 1., 0., 0.5));
+    let expected = LinearSrgb::from(Srgb::new(0.5, 1., 0., 0.5));
+    assert!((linear.red - expected.red * 0.5).abs() < 1e-6);
+    assert_eq!(linear.green, 0.5);
+    assert_color_approx_eq(Srgb::from(linear), Srgb::new(0.5, 1., 0., 0.5));
+    assert_color_approx_eq(premultiplied.convert::<Srgb>(), color);
+    assert_eq!(named::RED.convert::<PremultipliedLinearSrgb>().red, 1.);
+}
+
+#[test]
+fn filters() {
+    use crate::{BlendSpace, Filter};
+
+    assert_eq!(Filter::parse_list("none").unwrap(), vec![]);
+    assert_eq!(
+        Filter::parse_list(" grayscale(50%) SEPIA() hue-rotate(0.5turn)opacity(0.25) ").unwrap(),
+        vec![
+            Filter::Grayscale(0.5),
+            Filter::Sepia(1.),
+            Filter::HueRotate(180.),
+            Filter::Opacity(0.25),
+        ]
+    );
+    assert_eq!(
+        "hue-rotate(0)".parse::<Filter>().unwrap(),
+        Filter::HueRotate(0.)
+    );
+    assert_eq!(
+        "hue-rotate()".parse::<Filter>().unwrap(),
+        Filter::HueRotate(0.)
+    );
+    assert_eq!(
+        "saturate( 200% )".parse::<Filter>().unwrap(),
+        Filter::Saturate(2.)
+    );
+    for invalid in &[
+        "",
+        "grayscale(-1)",
+        "hue-rotate(90)",
+        "blur(2px)",
+        "none grayscale()",
+        "grayscale(1",
+        "brightness(1, 2)",
+    ] {
+        assert!(Filter::parse_list(invalid).is_err(), "{}", invalid);
+    }
+
+    let red = named::RED;
+    let apply = |filter: &str, color: Srgb| {
+        color.filter(&Filter::parse_list(filter).unwrap(), BlendSpace::Srgb)
+    };
+    assert_color_approx_eq(
+        apply("grayscale()", red),
+        Srgb::new(0.2126, 0.2126, 0.2126, 1.),
+    );
+    assert_color_approx_eq(apply("grayscale(200%)", red), apply("grayscale(1)", red));
+    assert_color_approx_eq(apply("grayscale(0)", red), red);
+    assert_color_approx_eq(apply("sepia()", named::WHITE), Srgb::new(1., 1., 0.937, 1.));
+    assert_color_approx_eq(
+        apply("saturate(0)", red),
+        Srgb::new(0.213, 0.213, 0.213, 1.),
+    );
+    assert_color_approx_eq(apply("hue-rotate(0deg)", red), red);
+    assert_color_approx_eq(
+        apply("hue-rotate(180deg)", red),
+        Srgb::new(0., 0.426, 0.426, 1.),
+    );
+    assert_color_approx_eq(apply("hue-rotate(360deg)", red), red);
+    assert_color_approx_eq(apply("invert()", red), named::AQUA);
+    assert_color_approx_eq(apply("invert(50%)", red), Srgb::new(0.5, 0.5, 0.5, 1.));
+    assert_color_approx_eq(apply("opacity(50%)", red), Srgb::new(1., 0., 0., 0.5));
+    assert_color_approx_eq(
+        apply("brightness(0.5)", named::WHITE),
+        Srgb::new(0.5, 0.5, 0.5, 1.),
+    );
+    assert_color_approx_eq(
+        apply("brightness(3)", Srgb::new(0.2, 0.5, 0., 1.)),
+        Srgb::new(0.6, 1., 0., 1.),
+    );
+    assert_color_approx_eq(apply("contrast(0)", red), Srgb::new(0.5, 0.5, 0.5, 1.));
+    assert_color_approx_eq(
+        apply("contrast(2)", Srgb::new(0.25, 0.5, 0.6, 1.)),
+        Srgb::new(0., 0.5, 0.7, 1.),
+    );
+    // Filters apply in order.
+    assert_color_approx_eq(
+        apply("invert() brightness(0.5)", named::WHITE),
+        named::BLACK,
+    );
+    assert_color_approx_eq(
+        apply("brightness(0.5) invert()", named::WHITE),
+        Srgb::new(0.5, 0.5, 0.5, 1.),
+    );
+
+    // Arbitrary color matrices, here swapping red and blue and halving alpha.
+    #[rustfmt::skip]
+    let swap = Filter::ColorMatrix([
+        0., 0., 1., 0., 0.,
+        0., 1., 0., 0., 0.,
+        1., 0., 0., 0., 0.,
+        0., 0., 0., 0.5, 0.,
+    ]);
+    assert_color_approx_eq(
+        swap.apply(Srgb::new(1., 0.5, 0.25, 1.), BlendSpace::Srgb),
+        Srgb::new(0.25, 0.5, 1., 0.5),
+    );
+    let grayscale = Filter::ColorMatrix(Filter::Grayscale(0.3).matrix());
+    assert_eq!(
+        grayscale.apply(red, BlendSpace::Srgb),
+        Filter::Grayscale(0.3).apply(red, BlendSpace::Srgb)
+    );
+
+    // In linear-light sRGB, halving the brightness of white gives a lighter gray.
+    let half = Filter::Brightness(0.5);
+    assert_color_approx_eq(
+        half.apply(named::WHITE, BlendSpace::SrgbLinear),
+        Srgb::from(LinearSrgb::new(0.5, 0.5, 0.5, 1.)),
+    );
+    assert_color_approx_eq(
+        swap.apply(Srgb::new(1., 0.5, 0.25, 1.), BlendSpace::SrgbLinear),
+        Srgb::new(0.25, 0.5, 1., 0.5),
+    );
+    assert_color_approx_eq(
+        named::WHITE.filter(&[half, Filter::Invert(1.)], BlendSpace::SrgbLinear),
+        Srgb::from(LinearSrgb::new(0.5, 0.5, 0.5, 1.)),
+    );
+}
+
+#[test]
+fn sass_less_functions() {
+    use crate::ColorChannels;
+
+    #[track_caller]
+    fn assert_serializes_as(color: Srgb, expected: &str) {
+        assert_eq!(
+            color.to_string(),
+            Srgb::from_str(expected).unwrap().to_string()
+        );
+    }
+    let color = |s: &str| Srgb::from_str(s).unwrap();
+
+    // Examples from the Sass and Less documentation.
+    assert_serializes_as(color("#336699").darken(0.1), "#264d73");
+    assert_serializes_as(color("#336699").lighten(0.2), "#6699cc");
+    assert_serializes_as(color("#855").saturate(0.2), "#9e3f3f");
+    assert_serializes_as(color("#036").desaturate(0.2), "#0a335c");
+    assert_serializes_as(color("#6b717f").spin(60.), "#796b7f");
+    assert_serializes_as(color("hsl(10, 90%, 50%)").spin(30.), "#f2a60d");
+    assert_serializes_as(color("hsl(10, 90%, 50%)").spin(-30.), "#f20d59");
+    assert_serializes_as(color("#6b717f").greyscale(), "#757575");
+    assert_serializes_as(
+        color("hsl(90, 90%, 50%)").fade(0.1),
+        "rgba(128, 242, 13, 0.1)",
+    );
+    assert_serializes_as(color("#036").mix(color("#d2e1dd"), 0.5), "#698aa2");
+    assert_serializes_as(color("#036").mix(color("#d2e1dd"), 0.75), "#355f84");
+    assert_serializes_as(
+        color("rgba(242, 236, 228, 0.5)").mix(color("#6b717f"), 0.5),
+        "rgba(141, 144, 152, 0.75)",
+    );
+    assert_serializes_as(color("#007fff").tint(0.5), "#80bfff");
+    assert_serializes_as(color("#007fff").shade(0.5), "#004080");
+
+    let adjust = ColorChannels {
+        red: Some(15. / 255.),
+        ..Default::default()
+    };
+    assert_serializes_as(color("#6b717f").adjust(&adjust).unwrap(), "#7a717f");
+    let adjust = ColorChannels {
+        red: Some(-10. / 255.),
+        blue: Some(10. / 255.),
+        ..Default::default()
+    };
+    assert_serializes_as(color("#d2e1dd").adjust(&adjust).unwrap(), "#c8e1e7");
+    let adjust = ColorChannels {
+        lightness: Some(-0.3),
+        alpha: Some(-0.4),
+        ..Default::default()
+    };
+    assert_serializes_as(
+        color("#998099").adjust(&adjust).unwrap(),
+        "rgba(71, 57, 71, 0.6)",
+    );
+
+    let scale = ColorChannels {
+        red: Some(0.15),
+        ..Default::default()
+    };
+    assert_serializes_as(color("#6b717f").scale(&scale).unwrap(), "#81717f");
+    let scale = ColorChannels {
+        lightness: Some(-0.1),
+        saturation: Some(0.1),
+        ..Default::default()
+    };
+    assert_serializes_as(color("#d2e1dd").scale(&scale).unwrap(), "#b3d4cb");
+    let scale = ColorChannels {
+        alpha: Some(-0.4),
+        ..Default::default()
+    };
+    assert_eq!(
+        color("#998099").scale(&scale).unwrap(),
+        Srgb::new(0.6, 128. / 255., 0.6, 0.6)
+    );
+
+    let change = ColorChannels {
+        red: Some(100. / 255.),
+        blue: Some(50. / 255.),
+        ..Default::default()
+    };
+    assert_serializes_as(color("#d2e1dd").change(&change).unwrap(), "#64e132");
+    let change = ColorChannels {
+        lightness: Some(0.3),
+        alpha: Some(0.5),
+        ..Default::default()
+    };
+    assert_serializes_as(
+        color("#998099").change(&change).unwrap(),
+        "rgba(85, 68, 85, 0.5)",
+    );
+    let change = ColorChannels {
+        hue: Some(120.),
+        blackness: Some(0.5),
+        ..Default::default()
+    };
+    assert_serializes_as(color("#f00").change(&change).unwrap(), "#008000");
+
+    // Channels of different models cannot be combined, and hues cannot be scaled.
+    let mixed = ColorChannels {
+        red: Some(0.1),
+        lightness: Some(0.1),
+        ..Default::default()
+    };
+    assert_eq!(named::RED.adjust(&mixed), None);
+    let hue = ColorChannels {
+        hue: Some(10.),
+        ..Default::default()
+    };
+    assert_eq!(named::RED.scale(&hue), None);
+    assert_eq!(
+        named::RED.change(&ColorChannels::default()),
+        Some(named::RED)
+    );
+}
+
+#[test]
+fn preprocessor_expressions() {
+    use crate::Preprocessor;
+
+    let mut preprocessor = Preprocessor::new();
+    preprocessor
+        .variables
+        .insert("brand".into(), Srgb::from_str("#336699").unwrap());
+    preprocessor
+        .variables
+        .insert("accent".into(), Srgb::from_str("#ff8000").unwrap());
+    let evaluate = |s: &str| preprocessor.evaluate(s).unwrap().to_string();
+    let css = |s: &str| Srgb::from_str(s).unwrap().to_string();
+
+    assert_eq!(evaluate("darken(#336699, 10%)"), css("#264d73"));
+    assert_eq!(evaluate("DARKEN( #336699 , 10 )"), css("#264d73"));
+    assert_eq!(evaluate("rgba($brand, .5)"), css("rgba(51, 102, 153, 0.5)"));
+    assert_eq!(evaluate("rgb($brand, 50%)"), css("rgba(51, 102, 153, 0.5)"));
+    assert_eq!(
+        evaluate("fade(@accent, 50%)"),
+        css("rgba(255, 128, 0, 0.5)")
+    );
+    assert_eq!(evaluate("mix(red, blue, 25%)"), css("rgb(64, 0, 191)"));
+    assert_eq!(evaluate("mix(red, blue)"), css("rgb(128, 0, 128)"));
+    assert_eq!(evaluate("lighten($brand, 20%)"), css("#6699cc"));
+    assert_eq!(evaluate("saturate(#855, 20%)"), css("#9e3f3f"));
+    assert_eq!(evaluate("desaturate(#036, 20%)"), css("#0a335c"));
+    assert_eq!(evaluate("adjust-hue(#6b717f, 60deg)"), css("#796b7f"));
+    assert_eq!(evaluate("spin(hsl(10, 90%, 50%), -30)"), css("#f20d59"));
+    assert_eq!(evaluate("tint(#007fff, 50%)"), css("#80bfff"));
+    assert_eq!(evaluate("shade(#007fff)"), css("#004080"));
+    assert_eq!(evaluate("greyscale(#6b717f)"), css("#757575"));
+    assert_eq!(
+        evaluate("fadein(rgba(0, 0, 0, 0.5), 10%)"),
+        css("rgba(0, 0, 0, 0.6)")
+    );
+    assert_eq!(
+        evaluate("fadeout(rgba(0, 0, 0, 0.5), 10%)"),
+        css("rgba(0, 0, 0, 0.4)")
+    );
+    assert_eq!(
+        evaluate("opacify(rgba(0, 0, 0, 0.5), 0.2)"),
+        css("rgba(0, 0, 0, 0.7)")
+    );
+    assert_eq!(
+        evaluate("transparentize(#000, .25)"),
+        css("rgba(0, 0, 0, 0.75)")
+    );
+
+    // CSS colors and nested expressions.
+    assert_eq!(
+        evaluate("rgba(255, 0, 0, 0.5)"),
+        css("rgba(255, 0, 0, 0.5)")
+    );
+    assert_eq!(evaluate("  @brand "), css("#336699"));
+    assert_eq!(
+        evaluate("darken(lighten($brand, 10%), 10%)"),
+        css("#336699")
+    );
+    assert_eq!(
+        evaluate("mix(fade(@accent, 50%), rgb(0 0 255), 50%)"),
+        css("rgba(64, 32, 191, 0.75)")
+    );
+
+    // Hyphens and underscores are interchangeable in variable names.
+    preprocessor
+        .variables
+        .insert("brand_primary".into(), named::RED);
+    let evaluate = |s: &str| preprocessor.evaluate(s).unwrap().to_string();
+    assert_eq!(evaluate("$brand-primary"), css("red"));
+    assert_eq!(evaluate("@brand_primary"), css("red"));
+    assert!(preprocessor.evaluate("$brandprimary").is_err());
+
+    // Deeply nested calls are rejected rather than overflowing the stack.
+    let nested = |n: usize| format!("{}red{}", "greyscale(".repeat(n), ")".repeat(n));
+    assert_eq!(evaluate(&nested(64)), css("gray"));
+    assert!(preprocessor.evaluate(&nested(65)).is_err());
+    assert!(preprocessor.evaluate(&nested(100_000)).is_err());
+
+    for invalid in &[
+        "",
+        "$missing",
+        "$",
+        "darken(#336699)",
+        "darken(#336699, 10%, 1)",
+        "darken(#336699, 10px)",
+        "spin(red, 10px)",
+        "lighten(notacolor, 10%)",
+        "unknown(red)",
+        "darken(#336699, 10%",
+    ] {
+        assert!(preprocessor.evaluate(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn cam16_and_hct() {
+    use crate::Hct;
+
+    #[track_caller]
+    fn assert_approx(expected: [f32; 3], actual: [f32; 3], epsilon: f32) {
+        for i in 0..3 {
+            assert!((expected[i] - actual[i]).abs() <= epsilon, "{:?}", actual);
+        }
+    }
+
+    // Reference values from Material Color Utilities.
+    let hct = |hex: &str| Hct::from(Srgb::from_str(hex).unwrap());
+    let red = hct("#ff0000");
+    assert_approx(
+        [27.4082, 113.3579, 53.2329],
+        [red.hue, red.chroma, red.tone],
+        1e-3,
+    );
+    let blue = hct("#0000ff");
+    assert_approx(
+        [282.7881, 87.2301, 32.3026],
+        [blue.hue, blue.chroma, blue.tone],
+        1e-3,
+    );
+    let green = hct("#00ff00");
+    assert_approx(
+        [142.1399, 108.4101, 87.737],
+        [green.hue, green.chroma, green.tone],
+        1e-3,
+    );
+    let white = hct("#ffffff");
+    assert_approx(
+        [209.492, 2.869, 100.],
+        [white.hue, white.chroma, white.tone],
+        1e-3,
+    );
+    let cam = Cam16::from(Srgb::from_str("#ff0000").unwrap());
+    assert_approx(
+        [46.4452, 113.3579, 27.4082],
+        [cam.lightness, cam.chroma, cam.hue],
+        1e-3,
+    );
+    assert_eq!(0., cam.distance(&cam));
+
+    // Chromas outside the sRGB gamut are reduced, keeping the hue and tone.
+    let vivid = Hct::new(282.7881, 200., 32.3026, 1.).convert::<Srgb>();
+    assert_eq!(vivid.to_string(), "rgb(0, 0, 255)");
+    let gray = Hct::new(0., 0., 50., 1.).convert::<Srgb>();
+    assert_eq!(gray.to_string(), "rgb(119, 119, 119)");
+    for (_, color) in named_colors() {
+        let back = Srgb::from(color.convert::<Hct>().convert::<LinearSrgb>());
+        assert!(color.approx_eq(back, 0.1));
+    }
+}
+
+#[test]
+fn material_schemes() {
+    use crate::contrast_ratio;
+    use crate::material::{Scheme, TonalPalette, Variant};
+
+    let blue = Srgb::from_str("#0000ff").unwrap();
+    let palette = TonalPalette::from(blue);
+    let tones: Vec<String> = [100., 95., 90., 80., 70., 60., 50., 40., 30., 20., 10., 0.]
+        .iter()
+        .map(|tone| palette.tone(*tone).to_string())
+        .collect();
+    let expected: Vec<String> = [
+        "#ffffff", "#f1efff", "#e0e0ff", "#bec2ff", "#9da3ff", "#7c84ff", "#5a64ff", "#343dff",
+        "#0000ef", "#0001ac", "#00006e", "#000000",
+    ]
+    .iter()
+    .map(|hex| Srgb::from_str(hex).unwrap().to_string())
+    .collect();
+    assert_eq!(tones, expected);
+
+    // Reference values from Material Color Utilities.
+    let hex = |color: Srgb| color.to_string();
+    let css = |s: &str| Srgb::from_str(s).unwrap().to_string();
+    let light = Scheme::tonal_spot(blue, false);
+    assert_eq!(hex(light.primary), css("#555992"));
+    assert_eq!(hex(light.primary_container), css("#e0e0ff"));
+    assert_eq!(hex(light.on_primary_container), css("#11144b"));
+    assert_eq!(hex(light.secondary), css("#5c5d72"));
+    assert_eq!(hex(light.tertiary), css("#78536b"));
+    assert_eq!(hex(light.surface), css("#fbf8ff"));
+    assert_eq!(hex(light.on_surface), css("#1b1b21"));
+    let dark = Scheme::tonal_spot(blue, true);
+    assert_eq!(hex(dark.primary), css("#bec2ff"));
+    assert_eq!(hex(dark.primary_container), css("#3e4278"));
+    assert_eq!(hex(dark.on_primary_container), css("#e0e0ff"));
+    assert_eq!(hex(dark.surface), css("#131318"));
+    assert_eq!(hex(dark.on_surface), css("#e4e1e9"));
+
+    // SchemeVibrantTest.
+    let light = Scheme::vibrant(blue, false);
+    assert_eq!(hex(light.primary), css("#343dff"));
+    assert_eq!(hex(light.primary_container), css("#e0e0ff"));
+    assert_eq!(hex(light.on_primary_container), css("#00006e"));
+    assert_eq!(hex(light.surface), css("#fbf8ff"));
+    assert_eq!(
+        hex(Scheme::new(blue, Variant::Vibrant, false, -1.).primary),
+        css("#5660ff")
+    );
+    let dark = Scheme::vibrant(blue, true);
+    assert_eq!(hex(dark.primary), css("#bec2ff"));
+    assert_eq!(hex(dark.primary_container), css("#0000ef"));
+    assert_eq!(hex(dark.on_primary_container), css("#e0e0ff"));
+    assert_eq!(hex(dark.surface), css("#12131c"));
+
+    // SchemeExpressiveTest.
+    let light = Scheme::expressive(blue, false);
+    assert_eq!(hex(light.primary), css("#146c48"));
+    assert_eq!(hex(light.primary_container), css("#a2f4c6"));
+    assert_eq!(hex(light.surface), css("#fdf7ff"));
+    assert_eq!(
+        hex(Scheme::new(blue, Variant::Expressive, false, -1.).primary),
+        css("#32835d")
+    );
+    let dark = Scheme::expressive(blue, true);
+    assert_eq!(hex(dark.primary), css("#87d7ab"));
+    assert_eq!(hex(dark.primary_container), css("#005234"));
+    assert_eq!(hex(dark.on_primary_container), css("#a2f4c6"));
+    assert_eq!(hex(dark.surface), css("#14121a"));
+
+    // SchemeContentTest and SchemeFidelityTest, which only differ in their tertiary palette.
+    for (light, dark, tertiary_container) in [
+        (
+            Scheme::content(blue, false),
+            Scheme::content(blue, true),
+            "#81009f",
+        ),
+        (
+            Scheme::fidelity(blue, false),
+            Scheme::fidelity(blue, true),
+            "#9d0002",
+        ),
+    ] {
+        assert_eq!(hex(light.primary), css("#0001bb"));
+        assert_eq!(hex(light.primary_container), css("#0000ff"));
+        assert_eq!(hex(light.tertiary_container), css(tertiary_container));
+        assert_eq!(hex(light.surface), css("#fbf8ff"));
+        assert_eq!(hex(light.on_surface), css("#1a1b26"));
+        assert_eq!(hex(dark.primary), css("#bec2ff"));
+        assert_eq!(hex(dark.surface), css("#12121d"));
+    }
+
+    let high = Scheme::new(blue, Variant::TonalSpot, false, 1.);
+    assert!(
+        contrast_ratio(high.primary, high.surface) > contrast_ratio(light.primary, light.surface)
+    );
+
+    for variant in [
+        Variant::TonalSpot,
+        Variant::Vibrant,
+        Variant::Expressive,
+        Variant::Content,
+        Variant::Fidelity,
+    ] {
+        for seed in ["#0000ff", "#6750a4", "#b3a100", "#ff8000", "#808080"] {
+            for dark in [false, true] {
+                for contrast_level in [-1., 0., 1.] {
+                    let seed = Srgb::from_str(seed).unwrap();
+                    let scheme = Scheme::new(seed, variant, dark, contrast_level);
+                    for (foreground, background) in [
+                        (scheme.on_primary, scheme.primary),
+                        (scheme.on_secondary_container, scheme.secondary_container),
+                        (scheme.on_tertiary_container, scheme.tertiary_container),
+                        (scheme.on_error, scheme.error),
+                        (scheme.on_surface, scheme.surface),
+                        (scheme.inverse_on_surface, scheme.inverse_surface),
+                        (scheme.on_primary_fixed, scheme.primary_fixed),
+                    ] {
+                        assert!(contrast_ratio(foreground, background) >= 4.4);
+                    }
+                    assert_eq!(dark, scheme.surface.relative_luminance() < 0.18);
+                }
+            }
+        }
+    }
+
+    // The tertiary color of content schemes avoids dark yellow-greens.
+    let olive = Srgb::from_str("#b3a100").unwrap();
+    let tertiary = Scheme::content(olive, false).tertiary_container;
+    let tertiary = tertiary.convert::<crate::Hct>();
+    assert!(!((90. ..=111.).contains(&tertiary.hue.round()) && tertiary.tone.round() < 65.));
+}
+
+#[test]
+fn named() {
+    for (name, color) in named_colors() {
+        assert_eq!(color, Srgb::from_str(&name).unwrap());
+        assert_eq!(color, Srgb::from_str(&name.to_uppercase()).unwrap());
+        {
+            let mut name = name.clone();
+            name.pop();
+            assert!(Srgb::from_str(&name).is_err());
+        }
+        {
+            let mut name = name.clone();
+            name.push('a');
+            assert!(Srgb::from_str(&name).is_err());
+        }
+    }
+}
+
+#[test]
+fn numeric() {
+    assert!(Srgb::from_str("rgb(6 36 216 / 100%)").is_ok());
+    assert!(Srgb::from_str("rgb(6. 36 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36. 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216. / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216 / 100.%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216 / 100%.)").is_err());
+
+    assert!(Srgb::from_str("rgb(6.e0 36 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36.e0 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216.e0 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216 / 100.e0%)").is_err());
+
+    assert!(Srgb::from_str("rgb(6e 36 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36e 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216e / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216 / 100e%)").is_err());
+
+    assert!(Srgb::from_str("rgb(6e+ 36 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36e+ 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216e+ / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216 / 100e+%)").is_err());
+
+    assert!(Srgb::from_str("rgb(6e- 36 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36e- 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216e- / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216 / 100e-%)").is_err());
+
+    assert!(Srgb::from_str("rgb(6e+0. 36 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36e+0. 216 / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216e+0. / 100%)").is_err());
+    assert!(Srgb::from_str("rgb(6 36 216 / 100e+0.%)").is_err());
+
+    assert_eq!(
+        Srgb {
+            red: 6. / 255.,
+            green: 0.36 / 255.,
+            blue: 216. / 255.,
+            alpha: 1.0
+        },
+        Srgb::from_str("rgb(6e+0.36+216/100%)").unwrap()
+    );
+
+    let nines = "9".repeat(999);
+    assert_eq!(
+        Srgb {
+            red: 100. / 255.,
+            green: 100. / 255.,
+            blue: 100. / 255.,
+            alpha: 0.5445618932859895,
+        },
+        Srgb::from_str(&format!(
+            "rgb({}e-997 {}e-997 {}e-997 / 5445618932859895362967233318697132813618813095743952975439298223406969961560047552942717636670910728746893019786283454139917900193169748259349067524939840552682198095012176093045431437495773903922425632551857520884625114624126588173520906670968542074438852601438992904761759703022688483745081090292688986958251711580854575674815074162979705098246243690189880319928315307816832576838178256307401454285988871020923752587330172447966674453785790265533466496640456213871241930958703059911787722565044368663670643970181259143319016472430928902201239474588139233890135329130660705762320235358869874608541509790266400643191187286648422874774910682648288516244021893172769161449825765517353755844373640588822904791244190695299838293263075467057383813882521706545084301049855505888186560731e-781)",
+            nines, nines, nines,
+        ))
+        .unwrap()
+    );
+
+    assert!(Srgb::from_str("rgb(inf 0 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 inf 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 inf / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 0 / inf)").is_err());
+
+    assert!(Srgb::from_str("rgb(INF 0 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 INF 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 INF / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 0 / INF)").is_err());
+
+    assert!(Srgb::from_str("rgb(infinity 0 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 infinity 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 infinity / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 0 / infinity)").is_err());
+
+    assert!(Srgb::from_str("rgb(INFINITY 0 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 INFINITY 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 INFINITY / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 0 / INFINITY)").is_err());
+
+    assert!(Srgb::from_str("rgb(nan 0 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 nan 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 nan / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 0 / nan)").is_err());
+
+    assert!(Srgb::from_str("rgb(NAN 0 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 NAN 0 / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 NAN / 0)").is_err());
+    assert!(Srgb::from_str("rgb(0 0 0 / NAN)").is_err());
+
+    // Numbers that overflow are infinite, which clamps like any other value.
+    assert_eq!(Srgb::from_str("rgb(1e39 0 0)").unwrap(), named::RED);
+    assert_eq!(Srgb::from_str("color(srgb 1e39 0 0)").unwrap(), named::RED);
+    // Infinite linear components convert to NaN channels, which clamp to 0.
+    assert_eq!(
+        Srgb::from_str("color(srgb-linear 1e39 1e39 1e39)").unwrap(),
+        named::BLACK
+    );
+    assert_eq!(
+        Srgb::new(f32::NAN, 0.5, f32::NAN, 1.).clip(),
+        Srgb::new(0., 0.5, 0., 1.)
+    );
+}
+
+#[test]
+fn overflow() {
+    // https://github.com/w3c/web-platform-tests/blob/master/2dcontext/fill-and-stroke-styles/2d.fillStyle.parse.rgb-clamp-3.html
+    assert_eq!(
+        Srgb {
+            red: 0.,
+            green: 1.,
+            blue: 0.,
+            alpha: 1.,
+        },
+        Srgb::from_str("rgb(-2147483649, 4294967298, -18446744073709551619)").unwrap()
+    );
+
+    let nines = "9".repeat(999);
+    assert_eq!(
+        Srgb {
+            red: 1.,
+            green: 1.,
+            blue: 1.,
+            alpha: 1.
+        },
+        Srgb::from_str(&format!("rgb({}, {}, {}, {})", nines, nines, nines, nines)).unwrap()
+    );
+    assert_eq!(
+        Srgb {
+            red: 1.,
+            green: 1.,
+            blue: 1.,
+            alpha: 1.
+        },
+        Srgb::from_str("rgb(1e+999 1e+999 1e+999 / 1e+999)").unwrap()
+    );
+    assert_eq!(
+        Srgb {
+            red: 100. / 255.,
+            green: 100. / 255.,
+            blue: 100. / 255.,
             alpha: 0.5445618932859895,
         },
         Srgb::from_str(&format!(
@@ -861,20 +3085,8 @@ fn bench_named_all(b: &mut test::Bencher) {
 }
 
 fn named_colors() -> Vec<(String, Srgb)> {
-    let named_colors: Vec<serde_json::Value> =
-        serde_json::from_str(include_str!("tests/named_colors.json")).unwrap();
-    let mut result: Vec<_> = named_colors
-        .chunks(2)
-        .map(|named| {
-            (
-                named[0].as_str().unwrap().into(),
-                Srgb::from_rgb8(
-                    named[1][0].as_u64().unwrap() as u8,
-                    named[1][1].as_u64().unwrap() as u8,
-                    named[1][2].as_u64().unwrap() as u8,
-                ),
-            )
-        })
+    let mut result: Vec<_> = crate::named_colors()
+        .map(|(name, color)| (name.to_string(), color))
         .collect();
     result.push(("transparent".to_string(), Srgb::from_rgba8(0, 0, 0, 0)));
     result
@@ -28,6 +28,9 @@ macro_rules! test_computed_value {
             computed_value(color_from_css($specified).unwrap()),
             computed_value(Srgb::from_str($computed).unwrap()),
         );
+        if $property == "color" {
+            assert_eq!(color_from_css($specified).unwrap().to_string(), $computed);
+        }
     };
     ($property:expr, $specified:expr, $computed:expr, $title:expr) => {
         assert_eq!(
@@ -36,6 +39,14 @@ macro_rules! test_computed_value {
             "{}",
             $title,
         );
+        if $property == "color" {
+            assert_eq!(
+                color_from_css($specified).unwrap().to_string(),
+                $computed,
+                "{}",
+                $title,
+            );
+        }
     };
 }
 
@@ -57,6 +68,7 @@ macro_rules! test_valid_value {
             computed_value(color_from_css($value).unwrap()),
             Srgb::from_str($expected).unwrap(),
         );
+        assert_eq!(color_from_css($value).unwrap().to_string(), $expected);
     };
 }
 
@@ -0,0 +1,175 @@
+use crate::space::ColorSpace;
+use crate::{LinearSrgb, Srgb};
+
+/// A color in the CIE XYZ color space with a D65 white point.
+///
+/// <https://www.w3.org/TR/css-color-4/#predefined-xyz>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct XyzD65 {
+    /// The X component.
+    pub x: f32,
+    /// The Y component.
+    pub y: f32,
+    /// The Z component.
+    pub z: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+/// A color in the CIE XYZ color space with a D50 white point.
+///
+/// <https://www.w3.org/TR/css-color-4/#predefined-xyz>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct XyzD50 {
+    /// The X component.
+    pub x: f32,
+    /// The Y component.
+    pub y: f32,
+    /// The Z component.
+    pub z: f32,
+    /// The alpha component.
+    pub alpha: f32,
+}
+
+impl XyzD65 {
+    pub fn new(x: f32, y: f32, z: f32, alpha: f32) -> XyzD65 {
+        XyzD65 { x, y, z, alpha }
+    }
+}
+
+impl XyzD50 {
+    pub fn new(x: f32, y: f32, z: f32, alpha: f32) -> XyzD50 {
+        XyzD50 { x, y, z, alpha }
+    }
+}
+
+pub(crate) type Matrix = [[f64; 3]; 3];
+
+pub(crate) fn multiply(m: &Matrix, v: [f32; 3]) -> [f32; 3] {
+    let v = [v[0] as f64, v[1] as f64, v[2] as f64];
+    let row = |r: &[f64; 3]| (r[0] * v[0] + r[1] * v[1] + r[2] * v[2]) as f32;
+    [row(&m[0]), row(&m[1]), row(&m[2])]
+}
+
+// https://www.w3.org/TR/css-color-4/#color-conversion-code
+const LINEAR_SRGB_TO_XYZ_D65: Matrix = [
+    [506752. / 1228815., 87881. / 245763., 12673. / 70218.],
+    [87098. / 409605., 175762. / 245763., 12673. / 175545.],
+    [7918. / 409605., 87881. / 737289., 1001167. / 1053270.],
+];
+
+const XYZ_D65_TO_LINEAR_SRGB: Matrix = [
+    [12831. / 3959., -329. / 214., -1974. / 3959.],
+    [-851781. / 878810., 1648619. / 878810., 36519. / 878810.],
+    [705. / 12673., -2585. / 12673., 705. / 667.],
+];
+
+// Bradford chromatic adaptation.
+#[allow(clippy::excessive_precision)]
+const XYZ_D65_TO_D50: Matrix = [
+    [
+        1.0479297925449969,
+        0.022946870601609652,
+        -0.05019226628920524,
+    ],
+    [
+        0.02962780877005599,
+        0.9904344267538799,
+        -0.017073799063418826,
+    ],
+    [
+        -0.009243040646204504,
+        0.015055191490298152,
+        0.7518742814281371,
+    ],
+];
+
+#[allow(clippy::excessive_precision)]
+const XYZ_D50_TO_D65: Matrix = [
+    [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+    [
+        -0.0283697093338637,
+        1.0099953980813041,
+        0.021041441191917323,
+    ],
+    [
+        0.012314014864481998,
+        -0.020507649298898964,
+        1.330365926242124,
+    ],
+];
+
+impl From<LinearSrgb> for XyzD65 {
+    fn from(linear: LinearSrgb) -> Self {
+        let [x, y, z] = multiply(
+            &LINEAR_SRGB_TO_XYZ_D65,
+            [linear.red, linear.green, linear.blue],
+        );
+        XyzD65::new(x, y, z, linear.alpha)
+    }
+}
+
+impl From<XyzD65> for LinearSrgb {
+    fn from(xyz: XyzD65) -> Self {
+        let [red, green, blue] = multiply(&XYZ_D65_TO_LINEAR_SRGB, [xyz.x, xyz.y, xyz.z]);
+        LinearSrgb::new(red, green, blue, xyz.alpha)
+    }
+}
+
+impl From<Srgb> for XyzD65 {
+    fn from(srgb: Srgb) -> Self {
+        XyzD65::from(LinearSrgb::from(srgb))
+    }
+}
+
+impl From<XyzD65> for Srgb {
+    fn from(xyz: XyzD65) -> Self {
+        Srgb::from(LinearSrgb::from(xyz))
+    }
+}
+
+impl From<XyzD65> for XyzD50 {
+    fn from(xyz: XyzD65) -> Self {
+        let [x, y, z] = multiply(&XYZ_D65_TO_D50, [xyz.x, xyz.y, xyz.z]);
+        XyzD50::new(x, y, z, xyz.alpha)
+    }
+}
+
+impl From<XyzD50> for XyzD65 {
+    fn from(xyz: XyzD50) -> Self {
+        let [x, y, z] = multiply(&XYZ_D50_TO_D65, [xyz.x, xyz.y, xyz.z]);
+        XyzD65::new(x, y, z, xyz.alpha)
+    }
+}
+
+impl From<Srgb> for XyzD50 {
+    fn from(srgb: Srgb) -> Self {
+        XyzD50::from(XyzD65::from(srgb))
+    }
+}
+
+impl From<XyzD50> for Srgb {
+    fn from(xyz: XyzD50) -> Self {
+        Srgb::from(XyzD65::from(xyz))
+    }
+}
+
+impl ColorSpace for XyzD65 {
+    fn to_xyz(self) -> XyzD65 {
+        self
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        xyz
+    }
+}
+
+impl ColorSpace for XyzD50 {
+    fn to_xyz(self) -> XyzD65 {
+        XyzD65::from(self)
+    }
+
+    fn from_xyz(xyz: XyzD65) -> Self {
+        XyzD50::from(xyz)
+    }
+}